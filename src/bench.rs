@@ -0,0 +1,173 @@
+//! `bujo bench`: times lookups, rendering and search on a large generated
+//! journal, to check the index keeps the TUI interactive.
+
+use crate::config::{ConfigManager, JournalLocation, StorageBackend};
+use crate::index::JournalIndex;
+use crate::models::{BulletType, Entry, Journal};
+use crate::query::Query;
+use crate::storage::Storage;
+use crate::ui::app::{App, AppTab};
+use anyhow::Result;
+use chrono::{Datelike, Days, Local};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+use std::time::{Duration, Instant};
+
+/// Slower than this and typing or scrolling starts to feel laggy.
+const INTERACTIVE: Duration = Duration::from_millis(50);
+const ROUNDS: usize = 20;
+/// Entries are spread over this many days up to today.
+const DAYS: u64 = 3 * 365;
+
+const VERBS: &[&str] = &["Review", "Call", "Email", "Draft", "Plan", "Book", "Pay", "Fix", "Read", "Sketch"];
+const OBJECTS: &[&str] = &[
+    "quarterly budget", "release notes", "dentist appointment", "garden layout", "flight to Lisbon",
+    "insurance renewal", "team offsite", "kitchen tap", "chapter three", "landing page",
+];
+const TAGS: &[&str] = &["#work", "#home", "#finance", "#health", "#travel", "@phone", "@office", "@errands"];
+
+pub fn run(entries: usize) -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("bujo-bench-{}", std::process::id()));
+    let result = run_in(&dir, entries);
+    let _ = std::fs::remove_dir_all(&dir);
+    result
+}
+
+fn run_in(dir: &std::path::Path, count: usize) -> Result<()> {
+    let location = JournalLocation {
+        name: None,
+        dir: dir.to_path_buf(),
+        backend: StorageBackend::Json,
+    };
+    Storage::new(&location)?.save_journal(&generate(count))?;
+
+    let started = Instant::now();
+    let mut app = App::open(ConfigManager::in_dir(dir.join("config"))?, location)?;
+    app.migration = None;
+    println!("Loaded {} entries in {} ms\n", app.journal.entries().len(), started.elapsed().as_millis());
+
+    let mut results = Vec::new();
+    let mut terminal = Terminal::new(TestBackend::new(120, 40))?;
+    let today = Local::now().date_naive();
+
+    // Opening the app built the journal's index already; this builds another.
+    results.push(time_once("build index", || {
+        JournalIndex::build(app.journal.entries());
+    }));
+
+    results.push(time("calendar month lookups", || {
+        for day in 0..42 {
+            app.journal.entries_for_date(today - Days::new(day));
+        }
+    }));
+
+    for (tab, name) in [(AppTab::Daily, "render daily log"), (AppTab::Monthly, "render monthly log")] {
+        app.current_tab = tab;
+        results.push(time(name, || {
+            let _ = terminal.draw(|f| crate::draw_ui(f, &app));
+        }));
+    }
+
+    for text in ["budget", "budgte", "rls", "type:task tag:finance priority:>2", "\"release notes\" -#home"] {
+        let query = Query::parse(text)?;
+        results.push(time(&format!("search {}", text), || {
+            app.journal.search_entries(&query);
+        }));
+    }
+
+    // Typing a query into the Search tab, one key and one frame at a time.
+    app.current_tab = AppTab::Search;
+    let mut slowest = Duration::ZERO;
+    let mut total = Duration::ZERO;
+    let typed = "quartrly budget #finance";
+    app.handle_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE))?;
+    for c in typed.chars() {
+        let started = Instant::now();
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))?;
+        terminal.draw(|f| crate::draw_ui(f, &app))?;
+        let elapsed = started.elapsed();
+        slowest = slowest.max(elapsed);
+        total += elapsed;
+    }
+    results.push((format!("type \"{}\" per key", typed), total / typed.len() as u32, slowest));
+    results.push(time(&format!("render {} search results", app.search_hits.len()), || {
+        let _ = terminal.draw(|f| crate::draw_ui(f, &app));
+    }));
+
+    let mut journal = app.journal.clone();
+    let date = today - Days::new(10);
+    results.push(time("add, edit and remove an entry", || {
+        let mut entry = Entry::new("Bench entry #bench".to_string(), BulletType::Task, date);
+        let id = entry.id;
+        journal.add_entry(entry.clone());
+        entry.set_content("Edited bench entry #bench".to_string());
+        journal.replace_entry(entry);
+        journal.remove_entry(id);
+    }));
+
+    println!("{:<48} {:>10} {:>10}", "", "median", "slowest");
+    for (name, median, slowest) in &results {
+        println!("{:<48} {:>7.2} ms {:>7.2} ms", name, millis(*median), millis(*slowest));
+    }
+    let laggy: Vec<&str> = results
+        .iter()
+        // The index is built once, while loading.
+        .filter(|(name, _, slowest)| name != "build index" && *slowest > INTERACTIVE)
+        .map(|(name, ..)| name.as_str())
+        .collect();
+    if laggy.is_empty() {
+        println!("\nEverything stayed under {} ms.", INTERACTIVE.as_millis());
+    } else {
+        println!("\nOver {} ms: {}", INTERACTIVE.as_millis(), laggy.join(", "));
+    }
+    Ok(())
+}
+
+/// A journal of `count` entries spread over the last few years, a few tagged and prioritised.
+fn generate(count: usize) -> Journal {
+    let today = Local::now().date_naive();
+    let mut journal = Journal::default();
+    let entries = journal.entries_mut();
+    for i in 0..count {
+        let date = today - Days::new(DAYS - (i as u64 * DAYS / count as u64));
+        let mut content = format!("{} {}", VERBS[i % VERBS.len()], OBJECTS[(i / 7) % OBJECTS.len()]);
+        if i % 3 == 0 {
+            content = format!("{} {}", content, TAGS[(i / 3) % TAGS.len()]);
+        }
+        let bullet_type = match i % 5 {
+            0 => BulletType::Event,
+            1 => BulletType::Note,
+            _ => BulletType::Task,
+        };
+        let mut entry = Entry::new(content, bullet_type, date);
+        if i % 11 == 0 {
+            entry.priority = Some((date.day() % 5 + 1) as u8);
+        }
+        entries.push(entry);
+    }
+    journal
+}
+
+/// Median and slowest of several runs.
+fn time(name: &str, mut run: impl FnMut()) -> (String, Duration, Duration) {
+    let mut samples: Vec<Duration> = (0..ROUNDS)
+        .map(|_| {
+            let started = Instant::now();
+            run();
+            started.elapsed()
+        })
+        .collect();
+    samples.sort();
+    (name.to_string(), samples[ROUNDS / 2], samples[ROUNDS - 1])
+}
+
+fn time_once(name: &str, run: impl FnOnce()) -> (String, Duration, Duration) {
+    let started = Instant::now();
+    run();
+    let elapsed = started.elapsed();
+    (name.to_string(), elapsed, elapsed)
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -0,0 +1,538 @@
+use crate::config::{ConfigManager, JournalLocation, StorageBackend};
+use crate::models::{normalize_tag, BulletType, Collection, Entry, SavedSearch, TaskStatus, MAX_PRIORITY};
+use crate::query::Query;
+use crate::storage::{backup_timestamp, is_encrypted_backup, Storage, ENCRYPTED_FILE};
+use anyhow::Result;
+use chrono::{Datelike, Local, NaiveDate};
+use clap::{Args, Subcommand, ValueEnum};
+use serde::Serialize;
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// Add an entry without opening the TUI
+    Add {
+        #[arg(value_enum, help = "Kind of entry to add")]
+        kind: EntryKind,
+        #[arg(help = "Entry content")]
+        content: String,
+        #[arg(short, long, help = "Date to log the entry on (YYYY-MM-DD, default: today)")]
+        date: Option<NaiveDate>,
+        #[arg(short, long, value_parser = parse_month, conflicts_with = "date", help = "Plan the entry for a whole month instead of a day (YYYY-MM)")]
+        month: Option<(i32, u32)>,
+        #[arg(long = "tag", value_name = "TAG", help = "Tag to attach (repeatable)")]
+        tags: Vec<String>,
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=MAX_PRIORITY as i64), help = "Priority of the entry, 1 to 5")]
+        priority: Option<u8>,
+    },
+    /// Print entries for a day, month or date range
+    List {
+        #[command(flatten)]
+        selector: DateSelector,
+        #[arg(short, long, value_enum, help = "Only show tasks with this status")]
+        status: Option<StatusFilter>,
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text, help = "Output format")]
+        format: OutputFormat,
+    },
+    /// Search entry content and tags
+    Search {
+        #[arg(required_unless_present = "saved", help = "Query, e.g. type:task tag:work date:2026-09..2026-10 priority:>2 \"exact phrase\" -exclude")]
+        query: Vec<String>,
+        #[arg(long, value_name = "NAME", conflicts_with = "query", help = "Run a saved search instead of a query")]
+        saved: Option<String>,
+        #[arg(long, value_name = "NAME", conflicts_with = "saved", help = "Save the query under this name, replacing any saved search of that name")]
+        save: Option<String>,
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text, help = "Output format")]
+        format: OutputFormat,
+    },
+    /// Print entries scheduled after today
+    Future {
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text, help = "Output format")]
+        format: OutputFormat,
+    },
+    /// Print all collections
+    Collections {
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Text, help = "Output format")]
+        format: OutputFormat,
+    },
+    /// List journal backups, or roll back to one
+    Restore {
+        #[arg(help = "Number of the backup to restore, as shown in the list")]
+        backup: Option<usize>,
+    },
+    /// Copy the journal into another storage backend and switch to it
+    Convert {
+        #[arg(value_enum, help = "Backend to move the journal to")]
+        to: BackendKind,
+        #[arg(long, help = "Overwrite a journal already stored in the target backend")]
+        force: bool,
+    },
+    /// Encrypt the journal with a passphrase, removing the plaintext copy and backups
+    Encrypt,
+    /// Store an encrypted journal as plain JSON again
+    Decrypt {
+        #[arg(long, help = "Overwrite an existing journal.json")]
+        force: bool,
+    },
+    /// Time lookups, rendering and search on a generated journal
+    #[cfg(feature = "bench")]
+    Bench {
+        #[arg(long, default_value_t = 100_000, help = "Number of entries to generate")]
+        entries: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// Bullet-formatted plain text
+    Text,
+    /// A single pretty-printed JSON array
+    Json,
+    /// One compact JSON object per line
+    Ndjson,
+}
+
+#[derive(Args)]
+pub struct DateSelector {
+    #[arg(long, conflicts_with_all = ["date", "month", "from", "to"], help = "Show today's entries (default)")]
+    today: bool,
+    #[arg(short, long, conflicts_with_all = ["month", "from", "to"], help = "Show entries for a single day (YYYY-MM-DD)")]
+    date: Option<NaiveDate>,
+    #[arg(short, long, value_parser = parse_month, conflicts_with_all = ["from", "to"], help = "Show entries for a month (YYYY-MM)")]
+    month: Option<(i32, u32)>,
+    #[arg(long, help = "Start of a date range, inclusive (YYYY-MM-DD)")]
+    from: Option<NaiveDate>,
+    #[arg(long, help = "End of a date range, inclusive (YYYY-MM-DD)")]
+    to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum EntryKind {
+    Task,
+    Event,
+    Note,
+}
+
+impl From<EntryKind> for BulletType {
+    fn from(kind: EntryKind) -> Self {
+        match kind {
+            EntryKind::Task => BulletType::Task,
+            EntryKind::Event => BulletType::Event,
+            EntryKind::Note => BulletType::Note,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum BackendKind {
+    Json,
+    Sqlite,
+    Markdown,
+}
+
+impl From<BackendKind> for StorageBackend {
+    fn from(kind: BackendKind) -> Self {
+        match kind {
+            BackendKind::Json => StorageBackend::Json,
+            BackendKind::Sqlite => StorageBackend::Sqlite,
+            BackendKind::Markdown => StorageBackend::Markdown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum StatusFilter {
+    Incomplete,
+    Complete,
+    Migrated,
+    Scheduled,
+    Irrelevant,
+}
+
+impl From<StatusFilter> for TaskStatus {
+    fn from(filter: StatusFilter) -> Self {
+        match filter {
+            StatusFilter::Incomplete => TaskStatus::Incomplete,
+            StatusFilter::Complete => TaskStatus::Complete,
+            StatusFilter::Migrated => TaskStatus::Migrated,
+            StatusFilter::Scheduled => TaskStatus::Scheduled,
+            StatusFilter::Irrelevant => TaskStatus::Irrelevant,
+        }
+    }
+}
+
+fn parse_month(value: &str) -> Result<(i32, u32), String> {
+    NaiveDate::parse_from_str(&format!("{}-01", value), "%Y-%m-%d")
+        .map(|date| (date.year(), date.month()))
+        .map_err(|_| format!("invalid month '{}', expected YYYY-MM", value))
+}
+
+/// Runs a command against the journal picked by `--journal`, or the default one.
+pub fn run(command: Commands, journal: Option<&str>) -> Result<()> {
+    let mut config = ConfigManager::new()?;
+    let location = config.get_config().resolve_journal(journal)?;
+
+    match command {
+        Commands::Add { kind, content, date, month, tags, priority } => {
+            add_entry(&location, kind, content, date, month, tags, priority)
+        }
+        Commands::List { selector, status, format } => list_entries(&location, selector, status, format),
+        Commands::Search { query, saved, save, format } => search_entries(&location, &query, saved, save, format),
+        Commands::Future { format } => list_future(&location, format),
+        Commands::Collections { format } => list_collections(&location, format),
+        Commands::Restore { backup } => {
+            let retention = config.get_config().journal.backup_retention;
+            restore_backup(&location, retention, backup)
+        }
+        Commands::Convert { to, force } => convert_storage(&mut config, &location, to.into(), force),
+        Commands::Encrypt => encrypt_journal(&mut config, &location),
+        Commands::Decrypt { force } => decrypt_journal(&mut config, &location, force),
+        #[cfg(feature = "bench")]
+        Commands::Bench { entries } => crate::bench::run(entries),
+    }
+}
+
+fn add_entry(
+    location: &JournalLocation,
+    kind: EntryKind,
+    content: String,
+    date: Option<NaiveDate>,
+    month: Option<(i32, u32)>,
+    tags: Vec<String>,
+    priority: Option<u8>,
+) -> Result<()> {
+    if content.trim().is_empty() {
+        anyhow::bail!("Entry content cannot be empty");
+    }
+
+    let storage = Storage::new(location)?;
+
+    let date = date.unwrap_or_else(|| Local::now().date_naive());
+    let mut entry = Entry::from_input(&content, kind.into(), date);
+    if let Some(first) = month.and_then(|(year, month)| NaiveDate::from_ymd_opt(year, month, 1)) {
+        entry.set_date(first, true);
+    }
+    entry.add_tags(tags.iter().map(|tag| normalize_tag(tag)).filter(|tag| !tag.is_empty()));
+    if priority.is_some() {
+        entry.priority = priority;
+    }
+
+    let symbol = entry.symbol();
+    let line = format!("{} {} {}", entry.date_label(), symbol, entry.content);
+    let _lock = storage.lock()?;
+    storage.upsert_entry(&entry)?;
+
+    println!("{}", line);
+    Ok(())
+}
+
+fn list_entries(
+    location: &JournalLocation,
+    selector: DateSelector,
+    status: Option<StatusFilter>,
+    format: OutputFormat,
+) -> Result<()> {
+    let storage = Storage::new(location)?;
+
+    let today = Local::now().date_naive();
+    let (from, to) = if let Some(date) = selector.date {
+        (Some(date), Some(date))
+    } else if let Some((year, month)) = selector.month {
+        let first = NaiveDate::from_ymd_opt(year, month, 1);
+        let last = first
+            .and_then(|first| first.checked_add_months(chrono::Months::new(1)))
+            .and_then(|next| next.pred_opt());
+        (first, last)
+    } else if !selector.today && (selector.from.is_some() || selector.to.is_some()) {
+        (selector.from, selector.to)
+    } else {
+        (Some(today), Some(today))
+    };
+    let mut entries = storage.entries_in_range(from, to)?;
+    // Like the daily log, a single day leaves out what is planned for its whole month.
+    if from == to {
+        entries.retain(|entry| !entry.whole_month);
+    }
+    entries.sort_by_key(|entry| (entry.date, !entry.whole_month));
+
+    if let Some(status) = status {
+        let status = TaskStatus::from(status);
+        entries.retain(|entry| entry.status == Some(status));
+    }
+
+    emit_entries(&entries.iter().collect::<Vec<_>>(), format)
+}
+
+fn search_entries(
+    location: &JournalLocation,
+    query: &[String],
+    saved: Option<String>,
+    save: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let storage = Storage::new(location)?;
+    let mut journal = storage.load_journal()?;
+
+    let text = match saved {
+        Some(name) => match journal.saved_search_named(&name) {
+            Some(search) => search.query.clone(),
+            None => {
+                let names: Vec<&str> = journal.sorted_saved_searches().iter().map(|search| search.name.as_str()).collect();
+                if names.is_empty() {
+                    anyhow::bail!("No saved search named '{}'; there are none yet", name);
+                }
+                anyhow::bail!("No saved search named '{}'; try {}", name, names.join(", "));
+            }
+        },
+        None => query.join(" "),
+    };
+    let query = Query::parse(&text)?;
+
+    if let Some(name) = save {
+        let _lock = storage.lock()?;
+        journal = storage.load_journal()?;
+        match journal.saved_searches.values_mut().find(|search| search.name == name) {
+            Some(search) => search.query = text,
+            None => {
+                let search = SavedSearch::new(name.clone(), text);
+                journal.saved_searches.insert(search.id, search);
+            }
+        }
+        storage.save_journal(&journal)?;
+        eprintln!("Saved search '{}'", name);
+    }
+
+    let entries: Vec<&Entry> = journal.search_entries(&query).into_iter().map(|(entry, _)| entry).collect();
+    emit_entries(&entries, format)
+}
+
+fn list_future(location: &JournalLocation, format: OutputFormat) -> Result<()> {
+    let storage = Storage::new(location)?;
+
+    let today = Local::now().date_naive();
+    let mut entries = storage.entries_in_range(today.succ_opt(), None)?;
+    entries.sort_by_key(|entry| (entry.date, !entry.whole_month));
+    emit_entries(&entries.iter().collect::<Vec<_>>(), format)
+}
+
+fn list_collections(location: &JournalLocation, format: OutputFormat) -> Result<()> {
+    let storage = Storage::new(location)?;
+    let journal = storage.load_journal()?;
+
+    let mut collections: Vec<&Collection> = journal.collections.values().collect();
+    collections.sort_by(|a, b| a.name.cmp(&b.name));
+    emit(&collections, format, print_collections)
+}
+
+fn emit_entries(entries: &[&Entry], format: OutputFormat) -> Result<()> {
+    emit(entries, format, print_entries)
+}
+
+fn emit<T: Serialize>(items: &[T], format: OutputFormat, print_text: fn(&[T])) -> Result<()> {
+    match format {
+        OutputFormat::Text => print_text(items),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(items)?),
+        OutputFormat::Ndjson => {
+            for item in items {
+                println!("{}", serde_json::to_string(item)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn print_collections(collections: &[&Collection]) {
+    for (i, collection) in collections.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{} ({} entries)", collection.name, collection.entries.len());
+        if let Some(description) = &collection.description {
+            println!("  {}", description);
+        }
+        for entry in &collection.entries {
+            println!("  {} {}", entry.symbol(), entry.content);
+        }
+    }
+}
+
+fn print_entries(entries: &[&Entry]) {
+    let mut current_date = None;
+    for entry in entries {
+        if current_date != Some((entry.date, entry.whole_month)) {
+            if current_date.is_some() {
+                println!();
+            }
+            if entry.whole_month {
+                println!("{}", entry.date.format("%B %Y"));
+            } else {
+                println!("{}", entry.date.format("%A, %B %d, %Y"));
+            }
+            current_date = Some((entry.date, entry.whole_month));
+        }
+        println!("  {} {}", entry.symbol(), entry.content);
+    }
+}
+
+fn restore_backup(location: &JournalLocation, retention: usize, backup: Option<usize>) -> Result<()> {
+    let storage = Storage::new(location)?;
+    let backups = storage.list_backups()?;
+
+    let Some(number) = backup else {
+        if backups.is_empty() {
+            println!("No backups yet. A backup is taken each time the journal is opened.");
+        }
+        for (i, path) in backups.iter().enumerate() {
+            let taken_at = backup_timestamp(path)
+                .map(|timestamp| timestamp.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default();
+            let summary = match storage.load_backup(path) {
+                Ok(journal) => format!(
+                    "{} entries, {} collections",
+                    journal.entries().len(),
+                    journal.collections.len()
+                ),
+                Err(_) => "unreadable".to_string(),
+            };
+            println!("{:>3}  {}  ({})", i + 1, taken_at, summary);
+        }
+        return Ok(());
+    };
+
+    let Some(path) = number.checked_sub(1).and_then(|index| backups.get(index)) else {
+        anyhow::bail!("No backup number {}; run `bujo restore` to list backups", number);
+    };
+
+    let journal = storage.load_backup(path)?;
+    let _lock = storage.lock()?;
+    // Keep the current journal around in case the restore was a mistake.
+    storage.backup_journal(retention)?;
+    storage.save_journal(&journal)?;
+
+    println!("Restored backup {} ({})", number, path.display());
+    Ok(())
+}
+
+fn convert_storage(
+    config: &mut ConfigManager,
+    location: &JournalLocation,
+    to: StorageBackend,
+    force: bool,
+) -> Result<()> {
+    let name = configured_name(location)?;
+    let from = location.backend;
+    if from == to {
+        anyhow::bail!("The journal is already stored in the {} backend", to.name());
+    }
+
+    let source = Storage::new(location)?;
+    let target = Storage::new(&JournalLocation { backend: to, ..location.clone() })?;
+    let _lock = source.lock()?;
+
+    let existing = target.load_journal()?;
+    if !force && (!existing.entries().is_empty() || !existing.collections.is_empty()) {
+        anyhow::bail!(
+            "The {} backend already holds a journal; pass --force to overwrite it",
+            to.name()
+        );
+    }
+
+    let journal = source.load_journal()?;
+    target.save_journal(&journal)?;
+    set_backend(config, &name, to)?;
+
+    println!(
+        "Copied {} entries and {} collections from {} to {}; the {} copy is left in place",
+        journal.entries().len(),
+        journal.collections.len(),
+        from.name(),
+        to.name(),
+        from.name()
+    );
+    Ok(())
+}
+
+/// The config name of a journal whose backend can be switched.
+fn configured_name(location: &JournalLocation) -> Result<String> {
+    match &location.name {
+        Some(name) => Ok(name.clone()),
+        None => anyhow::bail!(
+            "Only journals named in config.toml can be converted; add {} under [journals] first",
+            location.dir.display()
+        ),
+    }
+}
+
+fn set_backend(config: &mut ConfigManager, name: &str, backend: StorageBackend) -> Result<()> {
+    config.update_config(|config| match config.journals.get_mut(name) {
+        Some(named) => named.backend = backend,
+        None => config.journal.backend = backend,
+    })
+}
+
+fn encrypt_journal(config: &mut ConfigManager, location: &JournalLocation) -> Result<()> {
+    let name = configured_name(location)?;
+    if location.backend == StorageBackend::Encrypted {
+        anyhow::bail!("Journal {} is already encrypted", name);
+    }
+    let encrypted_path = location.dir.join(ENCRYPTED_FILE);
+    if encrypted_path.exists() {
+        anyhow::bail!(
+            "{} already exists; remove it or run `bujo decrypt` to recover it first",
+            encrypted_path.display()
+        );
+    }
+
+    let source = Storage::new(location)?;
+    let _lock = source.lock()?;
+    let journal = source.load_journal()?;
+    let target = Storage::new(&JournalLocation { backend: StorageBackend::Encrypted, ..location.clone() })?;
+    target.save_journal(&journal)?;
+    set_backend(config, &name, StorageBackend::Encrypted)?;
+
+    // Plaintext left behind would defeat the point, backups included.
+    source.remove_journal_files()?;
+    let plaintext_backups: Vec<_> = source.list_backups()?
+        .into_iter()
+        .filter(|path| !is_encrypted_backup(path))
+        .collect();
+    for backup in &plaintext_backups {
+        std::fs::remove_file(backup)?;
+    }
+
+    println!(
+        "Encrypted {} entries and {} collections into {}; removed the {} copy and {} plaintext backups",
+        journal.entries().len(),
+        journal.collections.len(),
+        encrypted_path.display(),
+        location.backend.name(),
+        plaintext_backups.len()
+    );
+    Ok(())
+}
+
+fn decrypt_journal(config: &mut ConfigManager, location: &JournalLocation, force: bool) -> Result<()> {
+    let name = configured_name(location)?;
+    if location.backend != StorageBackend::Encrypted {
+        anyhow::bail!("Journal {} is not encrypted", name);
+    }
+
+    let source = Storage::new(location)?;
+    let _lock = source.lock()?;
+    let journal = source.load_journal()?;
+    let target = Storage::new(&JournalLocation { backend: StorageBackend::Json, ..location.clone() })?;
+    let existing = target.load_journal()?;
+    if !force && (!existing.entries().is_empty() || !existing.collections.is_empty()) {
+        anyhow::bail!("journal.json already holds a journal; pass --force to overwrite it");
+    }
+
+    target.save_journal(&journal)?;
+    set_backend(config, &name, StorageBackend::Json)?;
+    source.remove_journal_files()?;
+
+    println!(
+        "Decrypted {} entries and {} collections into journal.json; existing backups stay encrypted",
+        journal.entries().len(),
+        journal.collections.len()
+    );
+    Ok(())
+}
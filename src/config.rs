@@ -1,14 +1,47 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use ratatui::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Name of the journal kept in the data directory unless `[journals.default]` moves it.
+pub const DEFAULT_JOURNAL: &str = "default";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     pub theme: Theme,
     pub layout: Layout,
     pub journal: JournalConfig,
+    #[serde(default)]
+    pub journals: BTreeMap<String, NamedJournal>,
+}
+
+/// A journal listed under `[journals.<name>]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedJournal {
+    /// Directory holding the journal; `~/` is expanded.
+    pub path: PathBuf,
+    #[serde(default)]
+    pub backend: StorageBackend,
+}
+
+/// Which journal to open and where it lives.
+#[derive(Debug, Clone)]
+pub struct JournalLocation {
+    /// Name of the journal in the config, or `None` for one opened by path.
+    pub name: Option<String>,
+    pub dir: PathBuf,
+    pub backend: StorageBackend,
+}
+
+impl JournalLocation {
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => self.dir.display().to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +78,46 @@ pub struct JournalConfig {
     pub auto_migrate_tasks: bool,
     pub date_format: String,
     pub default_view: String,
+    #[serde(default = "default_backup_retention")]
+    pub backup_retention: usize,
+    #[serde(default = "default_autosave_secs")]
+    pub autosave_secs: u64,
+    #[serde(default)]
+    pub backend: StorageBackend,
+}
+
+/// Where the journal is kept on disk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// A single `journal.json` file
+    #[default]
+    Json,
+    /// A `journal.db` SQLite database
+    Sqlite,
+    /// A `journal/` tree of Markdown files, one per day
+    Markdown,
+    /// A single `journal.enc` file encrypted with a passphrase
+    Encrypted,
+}
+
+impl StorageBackend {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StorageBackend::Json => "json",
+            StorageBackend::Sqlite => "sqlite",
+            StorageBackend::Markdown => "markdown",
+            StorageBackend::Encrypted => "encrypted",
+        }
+    }
+}
+
+fn default_backup_retention() -> usize {
+    10
+}
+
+fn default_autosave_secs() -> u64 {
+    5
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,16 +128,6 @@ pub enum BorderStyle {
     Double,
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            theme: Theme::default(),
-            layout: Layout::default(),
-            journal: JournalConfig::default(),
-        }
-    }
-}
-
 impl Default for Theme {
     fn default() -> Self {
         Self {
@@ -109,10 +172,72 @@ impl Default for JournalConfig {
             auto_migrate_tasks: false,
             date_format: "%Y-%m-%d".to_string(),
             default_view: "daily".to_string(),
+            backup_retention: default_backup_retention(),
+            autosave_secs: default_autosave_secs(),
+            backend: StorageBackend::default(),
         }
     }
 }
 
+impl Config {
+    /// The default journal followed by the named ones.
+    pub fn journal_names(&self) -> Vec<&str> {
+        let mut names = vec![DEFAULT_JOURNAL];
+        names.extend(self.journals.keys().map(String::as_str).filter(|name| *name != DEFAULT_JOURNAL));
+        names
+    }
+
+    /// Finds the journal meant by a `--journal` value: a name from `[journals]`,
+    /// `default`, or a directory path. Nothing selects the default journal.
+    pub fn resolve_journal(&self, selector: Option<&str>) -> Result<JournalLocation> {
+        let selector = selector.unwrap_or(DEFAULT_JOURNAL);
+
+        if let Some(named) = self.journals.get(selector) {
+            return Ok(JournalLocation {
+                name: Some(selector.to_string()),
+                dir: expand_home(&named.path),
+                backend: named.backend,
+            });
+        }
+
+        if selector == DEFAULT_JOURNAL {
+            let dir = dirs::data_dir()
+                .context("Could not find data directory")?
+                .join("bujo");
+            return Ok(JournalLocation {
+                name: Some(DEFAULT_JOURNAL.to_string()),
+                dir,
+                backend: self.journal.backend,
+            });
+        }
+
+        let looks_like_path = selector.contains(std::path::MAIN_SEPARATOR)
+            || selector.contains('/')
+            || selector.starts_with('.')
+            || selector.starts_with('~');
+        if !looks_like_path {
+            bail!(
+                "No journal named '{}'; known journals: {}",
+                selector,
+                self.journal_names().join(", ")
+            );
+        }
+
+        Ok(JournalLocation {
+            name: None,
+            dir: expand_home(&PathBuf::from(selector)),
+            backend: self.journal.backend,
+        })
+    }
+}
+
+fn expand_home(path: &std::path::Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
 impl ColorScheme {
     pub fn get_color(&self, color_name: &str) -> Color {
         match color_name.to_lowercase().as_str() {
@@ -194,7 +319,11 @@ impl ConfigManager {
         let config_dir = dirs::config_dir()
             .context("Could not find config directory")?
             .join("bujo");
-        
+        Self::in_dir(config_dir)
+    }
+
+    /// Uses `config.toml` in the given directory rather than the user's.
+    pub fn in_dir(config_dir: PathBuf) -> Result<Self> {
         fs::create_dir_all(&config_dir)
             .context("Could not create config directory")?;
         
@@ -251,6 +380,7 @@ impl ConfigManager {
         Ok(())
     }
 
+    #[allow(dead_code)]
     pub fn reset_to_defaults(&mut self) -> Result<()> {
         self.config = Config::default();
         self.save()?;
@@ -305,4 +435,52 @@ impl ConfigManager {
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_work_journal() -> Config {
+        let mut config = Config::default();
+        config.journals.insert("work".to_string(), NamedJournal {
+            path: PathBuf::from("/srv/journals/work"),
+            backend: StorageBackend::Markdown,
+        });
+        config
+    }
+
+    #[test]
+    fn names_from_the_config_win_over_paths() {
+        let config = config_with_work_journal();
+        let work = config.resolve_journal(Some("work")).unwrap();
+        assert_eq!(work.name.as_deref(), Some("work"));
+        assert_eq!(work.dir, PathBuf::from("/srv/journals/work"));
+        assert_eq!(work.backend, StorageBackend::Markdown);
+
+        let default = config.resolve_journal(None).unwrap();
+        assert_eq!(default.name.as_deref(), Some(DEFAULT_JOURNAL));
+        assert_eq!(default.backend, config.journal.backend);
+    }
+
+    #[test]
+    fn anything_that_looks_like_a_path_is_opened_as_a_directory() {
+        let config = config_with_work_journal();
+        for selector in ["./work", "../notes", "/tmp/scratch", "~/journal"] {
+            let location = config.resolve_journal(Some(selector)).unwrap();
+            assert_eq!(location.name, None, "{}", selector);
+            assert_eq!(location.backend, config.journal.backend);
+        }
+        let relative = config.resolve_journal(Some("./work")).unwrap();
+        assert_eq!(relative.dir, PathBuf::from("./work"));
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(config.resolve_journal(Some("~/journal")).unwrap().dir, home.join("journal"));
+        }
+    }
+
+    #[test]
+    fn unknown_names_list_the_known_journals() {
+        let error = config_with_work_journal().resolve_journal(Some("hobby")).unwrap_err().to_string();
+        assert!(error.contains("No journal named 'hobby'"), "{}", error);
+        assert!(error.contains("default, work"), "{}", error);
+    }
+}
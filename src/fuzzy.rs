@@ -0,0 +1,364 @@
+//! Typo-tolerant matching of one search term against a piece of text.
+//!
+//! A term matches, from best to worst, as an exact substring, as a tight
+//! subsequence of one word (`rls` finds "release"), or as a word within a
+//! typo or two of it (`relaese` finds "release"). Apart from exact matches
+//! of terms holding spaces or punctuation, a term matches text exactly when
+//! it matches one of the text's `words`, which is what lets the journal
+//! index look terms up by word.
+
+use std::ops::Range;
+
+const EXACT: i64 = 100;
+const WORD_START: i64 = 20;
+const WHOLE_WORD: i64 = 20;
+const SUBSEQUENCE: i64 = 60;
+const GAP_PENALTY: i64 = 5;
+const TYPO: i64 = 45;
+const TYPO_PENALTY: i64 = 15;
+
+/// How well a term matched, and the bytes of the text it matched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMatch {
+    pub score: i64,
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// A search term lowercased once, to be matched against many texts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Term {
+    text: String,
+    chars: Vec<char>,
+}
+
+impl Term {
+    pub fn new(text: &str) -> Self {
+        let chars = Folded::new(text).chars;
+        Self { text: chars.iter().collect(), chars }
+    }
+
+    /// The term as lowercased.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The term as a substring of the text, preferring one that starts a word.
+    pub fn find_exact(&self, text: &str) -> Option<TextMatch> {
+        exact(&self.chars, &Folded::new(text))
+    }
+
+    /// The term matched exactly, as a subsequence or with typos, whichever scores best.
+    pub fn find(&self, text: &str) -> Option<TextMatch> {
+        let term = &self.chars;
+        let text = Folded::new(text);
+        if missing(term, &text.chars) > typos_allowed(term.len()).unwrap_or(0) {
+            return None;
+        }
+        if let Some(exact) = exact(term, &text) {
+            return Some(exact);
+        }
+        let words = text.words();
+        match subsequence(term, &text, &words) {
+            // No typo match scores above `TYPO`.
+            Some(found) if found.score >= TYPO => Some(found),
+            found => match (found, typo(term, &text, &words)) {
+                (Some(a), Some(b)) => Some(if b.score > a.score { b } else { a }),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+
+    /// A cheap check that rules out most words `find` would not match: every
+    /// character of the term but as many as a typo match allows is in the word.
+    pub fn could_match(&self, word: &str) -> bool {
+        let allowed = typos_allowed(self.chars.len()).unwrap_or(0);
+        self.chars.iter().filter(|&&c| !word.contains(c)).nth(allowed).is_none()
+    }
+}
+
+/// Text lowercased a character at a time, remembering where each character
+/// starts unless the text is ASCII, where characters and bytes line up.
+struct Folded<'a> {
+    text: &'a str,
+    chars: Vec<char>,
+    starts: Option<Vec<usize>>,
+}
+
+impl<'a> Folded<'a> {
+    fn new(text: &'a str) -> Self {
+        if text.is_ascii() {
+            let chars = text.bytes().map(|b| b.to_ascii_lowercase() as char).collect();
+            return Self { text, chars, starts: None };
+        }
+        let (starts, chars) = text
+            .char_indices()
+            .map(|(start, c)| (start, c.to_lowercase().next().unwrap_or(c)))
+            .unzip();
+        Self { text, chars, starts: Some(starts) }
+    }
+
+    /// The byte range of the characters `from..to`.
+    fn bytes(&self, from: usize, to: usize) -> Range<usize> {
+        match &self.starts {
+            None => from..to,
+            Some(starts) => starts[from]..starts.get(to).copied().unwrap_or(self.text.len()),
+        }
+    }
+
+    fn starts_word(&self, i: usize) -> bool {
+        i == 0 || !self.chars[i - 1].is_alphanumeric()
+    }
+
+    fn ends_word(&self, i: usize) -> bool {
+        self.chars.get(i).is_none_or(|c| !c.is_alphanumeric())
+    }
+
+    /// Character ranges of the runs of letters and digits.
+    fn words(&self) -> Vec<Range<usize>> {
+        let mut words = Vec::new();
+        let mut start = None;
+        for (i, c) in self.chars.iter().enumerate() {
+            match (c.is_alphanumeric(), start) {
+                (true, None) => start = Some(i),
+                (false, Some(from)) => {
+                    words.push(from..i);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(from) = start {
+            words.push(from..self.chars.len());
+        }
+        words
+    }
+}
+
+/// The lowercased words of the text, as the index keys entries by.
+pub fn words(text: &str) -> Vec<String> {
+    let text = Folded::new(text);
+    text.words().into_iter().map(|word| text.chars[word].iter().collect()).collect()
+}
+
+fn exact(term: &[char], text: &Folded) -> Option<TextMatch> {
+    if term.is_empty() || term.len() > text.chars.len() {
+        return None;
+    }
+
+    let occurrences = (0..=text.chars.len() - term.len())
+        .filter(|&i| text.chars[i..i + term.len()] == term[..]);
+    let mut best: Option<(usize, i64)> = None;
+    for start in occurrences {
+        let end = start + term.len();
+        let mut score = EXACT;
+        if text.starts_word(start) {
+            score += WORD_START;
+            if text.ends_word(end) {
+                score += WHOLE_WORD;
+            }
+        }
+        if best.is_none_or(|(_, best)| score > best) {
+            best = Some((start, score));
+        }
+    }
+
+    best.map(|(start, score)| TextMatch {
+        score,
+        ranges: vec![text.bytes(start, start + term.len())],
+    })
+}
+
+/// How many characters of the term the text lacks.
+fn missing(term: &[char], text: &[char]) -> usize {
+    term.iter().filter(|c| !text.contains(c)).count()
+}
+
+/// The shortest stretch of one word containing the term's characters in order.
+fn subsequence(term: &[char], text: &Folded, words: &[Range<usize>]) -> Option<TextMatch> {
+    if term.len() < 3 {
+        return None;
+    }
+
+    let mut best: Option<Vec<usize>> = None;
+    for word in words {
+        if let Some(positions) = word_subsequence(term, &text.chars, word.clone()) {
+            let span = |positions: &[usize]| positions[positions.len() - 1] - positions[0];
+            if best.as_deref().is_none_or(|best| span(&positions) < span(best)) {
+                best = Some(positions);
+            }
+        }
+    }
+
+    let positions = best?;
+    let gaps = (positions[positions.len() - 1] - positions[0] + 1 - term.len()) as i64;
+    let mut score = SUBSEQUENCE - gaps * GAP_PENALTY;
+    if text.starts_word(positions[0]) {
+        score += WORD_START;
+    }
+    if score <= 0 || gaps as usize > term.len() {
+        return None;
+    }
+
+    // Neighbouring characters are highlighted as one run.
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for &i in &positions {
+        let range = text.bytes(i, i + 1);
+        match ranges.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => ranges.push(range),
+        }
+    }
+    Some(TextMatch { score, ranges })
+}
+
+/// Positions of the tightest match of the term's characters in order within `word`.
+fn word_subsequence(term: &[char], chars: &[char], word: Range<usize>) -> Option<Vec<usize>> {
+    // Each character is matched as early as it can be after the one before.
+    let positions_from = |start: usize| {
+        let mut next = start;
+        term.iter().map_while(move |&c| {
+            let i = (next..word.end).find(|&i| chars[i] == c)?;
+            next = i + 1;
+            Some(i)
+        })
+    };
+
+    let mut best: Option<(usize, usize)> = None;
+    for start in word.clone().filter(|&i| chars[i] == term[0]) {
+        let (found, last) = positions_from(start).fold((0, start), |(found, _), i| (found + 1, i));
+        if found < term.len() {
+            // Later starts cannot fit the term either.
+            break;
+        }
+        if best.is_none_or(|(best_start, best_last)| last - start < best_last - best_start) {
+            best = Some((start, last));
+        }
+    }
+    let (start, _) = best?;
+    Some(positions_from(start).collect())
+}
+
+/// How many typos a term of this many characters may contain, if any.
+fn typos_allowed(length: usize) -> Option<usize> {
+    match length {
+        0..=3 => None,
+        4..=7 => Some(1),
+        _ => Some(2),
+    }
+}
+
+/// A word, or the start of one, within a typo of the term; two for long terms.
+fn typo(term: &[char], text: &Folded, words: &[Range<usize>]) -> Option<TextMatch> {
+    let allowed = typos_allowed(term.len())?;
+
+    let mut best: Option<(usize, Range<usize>)> = None;
+    for word in words {
+        let chars = &text.chars[word.clone()];
+        if missing(term, chars) > allowed {
+            continue;
+        }
+        // A word still being typed is compared with the same length of word.
+        let lengths = [chars.len(), term.len().min(chars.len()), (term.len() + 1).min(chars.len())];
+        for (i, &length) in lengths.iter().enumerate() {
+            if lengths[..i].contains(&length) || length + allowed < term.len() {
+                continue;
+            }
+            let distance = edit_distance(term, &chars[..length]);
+            if distance <= allowed && best.as_ref().is_none_or(|(best, _)| distance < *best) {
+                best = Some((distance, word.start..word.start + length));
+            }
+        }
+    }
+
+    let (distance, word) = best?;
+    Some(TextMatch {
+        score: TYPO - distance as i64 * TYPO_PENALTY,
+        ranges: vec![text.bytes(word.start, word.end)],
+    })
+}
+
+/// Edits to turn `a` into `b`, counting a swap of neighbouring characters as one.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let width = b.len() + 1;
+    let mut rows = vec![0; (a.len() + 1) * width];
+    let at = |i: usize, j: usize| i * width + j;
+    for i in 0..=a.len() {
+        rows[at(i, 0)] = i;
+    }
+    for j in 0..=b.len() {
+        rows[at(0, j)] = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[at(i - 1, j)] + 1)
+                .min(rows[at(i, j - 1)] + 1)
+                .min(rows[at(i - 1, j - 1)] + substitution);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[at(i - 2, j - 2)] + 1);
+            }
+            rows[at(i, j)] = distance;
+        }
+    }
+    rows[at(a.len(), b.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(term: &str, text: &str) -> Option<i64> {
+        Term::new(term).find(text).map(|found| found.score)
+    }
+
+    /// The matched bytes as pairs, which read more easily in assertions than ranges.
+    fn ranges(term: &str, text: &str) -> Vec<(usize, usize)> {
+        let found = Term::new(term).find(text).map(|found| found.ranges).unwrap_or_default();
+        found.into_iter().map(|range| (range.start, range.end)).collect()
+    }
+
+    #[test]
+    fn typos_are_tolerated_by_term_length() {
+        assert_eq!(ranges("relaese", "Draft release notes"), [(6, 13)]);
+        assert_eq!(ranges("budgte", "Review budget"), [(7, 13)]);
+        assert!(score("relxxse", "Draft release notes").is_none());
+        // Long terms may hold two typos, short ones none.
+        assert!(score("qaurtelry", "Quarterly budget").is_some());
+        assert!(score("bdu", "budget").is_none());
+        // A word still being typed matches the start of a longer one.
+        assert_eq!(ranges("budgr", "Review budget"), [(7, 12)]);
+    }
+
+    #[test]
+    fn subsequences_stay_within_one_word() {
+        assert_eq!(ranges("rls", "Draft release notes"), [(6, 7), (8, 9), (11, 12)]);
+        assert_eq!(ranges("rlea", "Draft release notes"), [(6, 7), (8, 11)]);
+        assert!(score("dfn", "Draft final").is_none());
+        // Too spread out to be a deliberate abbreviation.
+        assert!(score("rtn", "rotation").is_none());
+    }
+
+    #[test]
+    fn matches_rank_from_exact_words_down_to_typos() {
+        let texts = ["Plan", "Planning", "Explanation", "Pleasant", "Plna"];
+        let scores: Vec<_> = texts.iter().map(|text| score("plan", text).unwrap()).collect();
+        assert!(scores.is_sorted_by(|a, b| a > b), "{:?}", scores);
+        assert!(score("rls", "Draft release") > score("rls", "Unrolls"));
+    }
+
+    #[test]
+    fn highlights_are_byte_ranges_of_the_original_text() {
+        assert_eq!(ranges("CRÈME", "Café crème"), [(6, 12)]);
+        assert_eq!(Term::new("crème").find_exact("CRÈME brûlée").map(|found| found.ranges[0].clone()), Some(0..6));
+        assert_eq!(ranges("brle", "Café brûlée"), [(6, 8), (10, 11), (13, 14)]);
+        // An exact match starting a word beats an earlier one inside a word.
+        assert_eq!(ranges("plan", "Explanation plan"), [(12, 16)]);
+    }
+
+    #[test]
+    fn words_split_on_anything_but_letters_and_digits() {
+        assert_eq!(words("Q4-plan, #Work @home"), ["q4", "plan", "work", "home"]);
+        assert!(Term::new("relaese").could_match("release"));
+        assert!(!Term::new("budget").could_match("bridge"));
+    }
+}
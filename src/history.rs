@@ -0,0 +1,222 @@
+use crate::models::{Collection, Entry, Journal, SavedSearch};
+use uuid::Uuid;
+
+const MAX_HISTORY: usize = 500;
+
+/// A reversible change to the journal.
+#[derive(Debug, Clone)]
+pub enum Operation {
+    AddEntry {
+        entry: Entry,
+        collection: Option<Uuid>,
+    },
+    DeleteEntry {
+        entry: Entry,
+        collection: Option<Uuid>,
+        index: usize,
+    },
+    UpdateEntry {
+        before: Entry,
+        after: Entry,
+    },
+    AddCollection(Collection),
+    DeleteCollection(Collection),
+    UpdateCollection {
+        before: Collection,
+        after: Collection,
+    },
+    AddSavedSearch(SavedSearch),
+    DeleteSavedSearch(SavedSearch),
+    UpdateSavedSearch {
+        before: SavedSearch,
+        after: SavedSearch,
+    },
+    Batch(Vec<Operation>),
+}
+
+impl Operation {
+    pub fn apply(&self, journal: &mut Journal) {
+        match self {
+            Operation::AddEntry { entry, collection } => {
+                insert_entry(journal, entry, *collection, None);
+            }
+            Operation::DeleteEntry { entry, .. } => {
+                journal.remove_entry(entry.id);
+            }
+            Operation::UpdateEntry { after, .. } => {
+                journal.replace_entry(after.clone());
+            }
+            Operation::AddCollection(collection) => {
+                journal.collections.insert(collection.id, collection.clone());
+            }
+            Operation::DeleteCollection(collection) => {
+                journal.collections.remove(&collection.id);
+            }
+            Operation::UpdateCollection { after, .. } => {
+                journal.collections.insert(after.id, after.clone());
+            }
+            Operation::AddSavedSearch(search) => {
+                journal.saved_searches.insert(search.id, search.clone());
+            }
+            Operation::DeleteSavedSearch(search) => {
+                journal.saved_searches.remove(&search.id);
+            }
+            Operation::UpdateSavedSearch { after, .. } => {
+                journal.saved_searches.insert(after.id, after.clone());
+            }
+            Operation::Batch(operations) => {
+                for operation in operations {
+                    operation.apply(journal);
+                }
+            }
+        }
+    }
+
+    pub fn revert(&self, journal: &mut Journal) {
+        match self {
+            Operation::AddEntry { entry, .. } => {
+                journal.remove_entry(entry.id);
+            }
+            Operation::DeleteEntry { entry, collection, index } => {
+                insert_entry(journal, entry, *collection, Some(*index));
+            }
+            Operation::UpdateEntry { before, .. } => {
+                journal.replace_entry(before.clone());
+            }
+            Operation::AddCollection(collection) => {
+                journal.collections.remove(&collection.id);
+            }
+            Operation::DeleteCollection(collection) => {
+                journal.collections.insert(collection.id, collection.clone());
+            }
+            Operation::UpdateCollection { before, .. } => {
+                journal.collections.insert(before.id, before.clone());
+            }
+            Operation::AddSavedSearch(search) => {
+                journal.saved_searches.remove(&search.id);
+            }
+            Operation::DeleteSavedSearch(search) => {
+                journal.saved_searches.insert(search.id, search.clone());
+            }
+            Operation::UpdateSavedSearch { before, .. } => {
+                journal.saved_searches.insert(before.id, before.clone());
+            }
+            Operation::Batch(operations) => {
+                for operation in operations.iter().rev() {
+                    operation.revert(journal);
+                }
+            }
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Operation::AddEntry { .. } => "add entry",
+            Operation::DeleteEntry { .. } => "delete entry",
+            Operation::UpdateEntry { .. } => "edit entry",
+            Operation::AddCollection(_) => "add collection",
+            Operation::DeleteCollection(_) => "delete collection",
+            Operation::UpdateCollection { .. } => "edit collection",
+            Operation::AddSavedSearch(_) => "save search",
+            Operation::DeleteSavedSearch(_) => "delete saved search",
+            Operation::UpdateSavedSearch { .. } => "edit saved search",
+            Operation::Batch(_) => "migration",
+        }
+    }
+}
+
+fn insert_entry(journal: &mut Journal, entry: &Entry, collection: Option<Uuid>, index: Option<usize>) {
+    let entry = entry.clone();
+    match (collection, index) {
+        (Some(collection_id), index) => {
+            if let Some(collection) = journal.collections.get_mut(&collection_id) {
+                let index = index.unwrap_or(collection.entries.len()).min(collection.entries.len());
+                collection.entries.insert(index, entry);
+            }
+        }
+        (None, Some(index)) => {
+            journal.insert_entry(index, entry);
+        }
+        (None, None) => journal.add_entry(entry),
+    }
+}
+
+/// Session-long undo and redo stacks of journal operations.
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<Operation>,
+    redo: Vec<Operation>,
+}
+
+impl History {
+    /// Records an operation that has already been applied to the journal.
+    pub fn record(&mut self, operation: Operation) {
+        self.undo.push(operation);
+        if self.undo.len() > MAX_HISTORY {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Whether there is nothing to undo or redo.
+    pub fn is_empty(&self) -> bool {
+        self.undo.is_empty() && self.redo.is_empty()
+    }
+
+    pub fn undo(&mut self, journal: &mut Journal) -> Option<&Operation> {
+        let operation = self.undo.pop()?;
+        operation.revert(journal);
+        self.redo.push(operation);
+        self.redo.last()
+    }
+
+    pub fn redo(&mut self, journal: &mut Journal) -> Option<&Operation> {
+        let operation = self.redo.pop()?;
+        operation.apply(journal);
+        self.undo.push(operation);
+        self.undo.last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merge::merge_journals;
+    use crate::models::{BulletType, JournalSettings};
+    use chrono::NaiveDate;
+    use std::collections::HashMap;
+
+    fn task(content: &str, day: u32) -> Entry {
+        Entry::new(content.to_string(), BulletType::Task, NaiveDate::from_ymd_opt(2026, 10, day).unwrap())
+    }
+
+    fn journal(entries: &[&Entry]) -> Journal {
+        let entries = entries.iter().map(|&entry| entry.clone()).collect();
+        Journal::new(entries, HashMap::new(), HashMap::new(), JournalSettings::default())
+    }
+
+    #[test]
+    fn undoing_a_delete_after_a_merge_keeps_the_log_in_date_order() {
+        let earlier = task("Earlier", 1);
+        let deleted = task("Deleted", 2);
+        let later = task("Later", 5);
+        let base = journal(&[&earlier, &deleted, &later]);
+
+        let mut mine = base.clone();
+        let mut history = History::default();
+        mine.remove_entry(deleted.id);
+        history.record(Operation::DeleteEntry { entry: deleted.clone(), collection: None, index: 1 });
+
+        // Someone else removed the first entry and added two on later days.
+        let added = [task("Added", 3), task("Added", 4)];
+        let theirs = journal(&[&deleted, &added[0], &added[1], &later]);
+        let mut merged = merge_journals(&base, &mine, &theirs).journal;
+
+        history.undo(&mut merged);
+        let dates: Vec<_> = merged.entries().iter().map(|entry| entry.date).collect();
+        assert!(dates.is_sorted(), "{:?}", dates);
+        for entry in [&deleted, &added[0], &added[1], &later] {
+            assert!(merged.get_entry(entry.id).is_some(), "{} went missing", entry.content);
+        }
+    }
+}
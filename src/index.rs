@@ -0,0 +1,287 @@
+//! Lookup tables over the daily log, so views and searches find entries
+//! without scanning all of them. `Journal` keeps its index in step with
+//! every change it makes, and builds it on first use after loading.
+
+use crate::fuzzy;
+use crate::models::Entry;
+use crate::query::Filter;
+use chrono::{Datelike, NaiveDate};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Bound;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default)]
+pub struct JournalIndex {
+    /// The date and id of each entry in log order, so positions in the log
+    /// are found without reading the entries themselves.
+    log: Vec<(NaiveDate, Uuid)>,
+    /// The day each entry is logged on, which with the log ordered by date
+    /// finds it by binary search.
+    dates: HashMap<Uuid, NaiveDate>,
+    by_date: BTreeMap<NaiveDate, HashSet<Uuid>>,
+    /// Entries by the words of their content and tags, as `fuzzy::words` splits them.
+    by_word: HashMap<String, HashSet<Uuid>>,
+    by_tag: BTreeMap<String, HashSet<Uuid>>,
+}
+
+impl JournalIndex {
+    pub fn build(entries: &[Entry]) -> Self {
+        let mut index = Self::default();
+        for entry in entries {
+            index.log.push((entry.date, entry.id));
+            index.add_keys(entry);
+        }
+        index
+    }
+
+    /// Records an entry added to the log at `position`.
+    pub fn insert(&mut self, position: usize, entry: &Entry) {
+        self.log.insert(position, (entry.date, entry.id));
+        self.add_keys(entry);
+    }
+
+    /// Forgets an entry removed from the log.
+    pub fn remove(&mut self, entry: &Entry) {
+        if let Some(position) = self.position(entry.id) {
+            self.log.remove(position);
+        }
+        self.remove_keys(entry);
+    }
+
+    /// Records an edit to an entry. One moved to another day goes last on it.
+    pub fn replace(&mut self, before: &Entry, after: &Entry) {
+        if before.date == after.date {
+            self.remove_keys(before);
+            self.add_keys(after);
+        } else {
+            self.remove(before);
+            let position = self.log.partition_point(|&(date, _)| date <= after.date);
+            self.insert(position, after);
+        }
+    }
+
+    fn remove_keys(&mut self, entry: &Entry) {
+        self.dates.remove(&entry.id);
+        if forget(self.by_date.get_mut(&entry.date), entry.id) {
+            self.by_date.remove(&entry.date);
+        }
+        for word in words_of(entry) {
+            if forget(self.by_word.get_mut(&word), entry.id) {
+                self.by_word.remove(&word);
+            }
+        }
+        for tag in &entry.tags {
+            if forget(self.by_tag.get_mut(tag), entry.id) {
+                self.by_tag.remove(tag);
+            }
+        }
+    }
+
+    fn add_keys(&mut self, entry: &Entry) {
+        self.dates.insert(entry.id, entry.date);
+        self.by_date.entry(entry.date).or_default().insert(entry.id);
+        for word in words_of(entry) {
+            self.by_word.entry(word).or_default().insert(entry.id);
+        }
+        for tag in &entry.tags {
+            self.by_tag.entry(tag.clone()).or_default().insert(entry.id);
+        }
+    }
+
+    pub fn date_of(&self, id: Uuid) -> Option<NaiveDate> {
+        self.dates.get(&id).copied()
+    }
+
+    /// Where the entry sits in the log, found among the entries of its day.
+    pub fn position(&self, id: Uuid) -> Option<usize> {
+        let date = self.date_of(id)?;
+        let start = self.log.partition_point(|&(other, _)| other < date);
+        self.log[start..]
+            .iter()
+            .take_while(|&&(other, _)| other == date)
+            .position(|&(_, other)| other == id)
+            .map(|offset| start + offset)
+    }
+
+    /// Where the entries with the given ids sit in the log, in log order.
+    pub fn positions(&self, ids: &HashSet<Uuid>) -> Vec<usize> {
+        // Past a few ids, one pass over the log beats finding each on its day.
+        if ids.len() < self.log.len() / 64 {
+            let mut positions: Vec<usize> = ids.iter().filter_map(|&id| self.position(id)).collect();
+            positions.sort_unstable();
+            return positions;
+        }
+        self.log
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, id))| ids.contains(id))
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Entries dated within the inclusive range; either end may be open.
+    pub fn in_range(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> impl Iterator<Item = &Uuid> {
+        let from = from.map_or(Bound::Unbounded, Bound::Included);
+        let to = to.map_or(Bound::Unbounded, Bound::Included);
+        let empty = matches!((from, to), (Bound::Included(from), Bound::Included(to)) if from > to);
+        let days = if empty { None } else { Some(self.by_date.range((from, to))) };
+        days.into_iter().flatten().flat_map(|(_, ids)| ids)
+    }
+
+    pub fn with_tag(&self, tag: &str) -> impl Iterator<Item = &Uuid> {
+        self.by_tag.get(tag).into_iter().flatten()
+    }
+
+    /// Every tag with the number of entries carrying it, by name.
+    pub fn tag_counts(&self) -> Vec<(&str, usize)> {
+        self.by_tag.iter().map(|(tag, ids)| (tag.as_str(), ids.len())).collect()
+    }
+
+    /// The entries that might match a filter, or `None` when it does not
+    /// narrow the search. Candidates still have to be checked against it.
+    pub fn candidates(&self, filter: &Filter) -> Option<HashSet<Uuid>> {
+        match filter {
+            Filter::All(filters) => filters
+                .iter()
+                .filter_map(|filter| self.candidates(filter))
+                .reduce(|a, b| a.intersection(&b).copied().collect()),
+            Filter::Tag(tag) => Some(
+                self.by_tag
+                    .iter()
+                    .filter(|(name, _)| name.eq_ignore_ascii_case(tag))
+                    .flat_map(|(_, ids)| ids.iter().copied())
+                    .collect(),
+            ),
+            // Whole-month entries are dated the first of the month they cover.
+            Filter::Date(from, to) => {
+                let from = from.map(|from| from.with_day(1).unwrap_or(from));
+                Some(self.in_range(from, *to).copied().collect())
+            }
+            Filter::Text(term) => {
+                let mut ids: HashSet<Uuid> = self.by_word
+                    .iter()
+                    .filter(|(word, _)| term.could_match(word) && term.find(word).is_some())
+                    .flat_map(|(_, ids)| ids.iter().copied())
+                    .collect();
+                // Punctuation can join words into one exact match, as in `q4-plan`.
+                if fuzzy::words(term.as_str()) != [term.as_str()] {
+                    ids.extend(self.containing_words(term.as_str())?);
+                }
+                Some(ids)
+            }
+            Filter::Phrase(phrase) => self.containing_words(phrase.as_str()),
+            Filter::Not(_) | Filter::Type(_) | Filter::Status(_) | Filter::Priority(..) => None,
+        }
+    }
+
+    /// Entries with a word containing each word of the text.
+    fn containing_words(&self, text: &str) -> Option<HashSet<Uuid>> {
+        fuzzy::words(text)
+            .iter()
+            .map(|piece| {
+                self.by_word
+                    .iter()
+                    .filter(|(word, _)| word.contains(piece.as_str()))
+                    .flat_map(|(_, ids)| ids.iter().copied())
+                    .collect::<HashSet<Uuid>>()
+            })
+            .reduce(|a, b| a.intersection(&b).copied().collect())
+    }
+}
+
+fn words_of(entry: &Entry) -> HashSet<String> {
+    fuzzy::words(&entry.content)
+        .into_iter()
+        .chain(entry.tags.iter().flat_map(|tag| fuzzy::words(tag)))
+        .collect()
+}
+
+/// Drops an id from a key's set, returning whether the key is now unused.
+fn forget(ids: Option<&mut HashSet<Uuid>>, id: Uuid) -> bool {
+    ids.is_some_and(|ids| {
+        ids.remove(&id);
+        ids.is_empty()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fuzzy::Term;
+    use crate::models::BulletType;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn note(content: &str, date: NaiveDate) -> Entry {
+        Entry::new(content.to_string(), BulletType::Note, date)
+    }
+
+    fn ids<'a>(found: impl IntoIterator<Item = &'a Uuid>) -> HashSet<Uuid> {
+        found.into_iter().copied().collect()
+    }
+
+    #[test]
+    fn insert_and_remove_keep_every_key_in_step() {
+        let first = note("Budget review #finance", day(1));
+        let second = note("Budget call @phone", day(3));
+        let mut index = JournalIndex::build(std::slice::from_ref(&first));
+
+        index.insert(1, &second);
+        assert_eq!(index.date_of(second.id), Some(day(3)));
+        assert_eq!(index.position(second.id), Some(1));
+        assert_eq!(ids(index.in_range(Some(day(2)), None)), HashSet::from([second.id]));
+        assert_eq!(index.tag_counts(), [("@phone", 1), ("finance", 1)]);
+        assert_eq!(index.candidates(&Filter::Text(Term::new("budget"))), Some(HashSet::from([first.id, second.id])));
+
+        index.remove(&first);
+        assert_eq!(index.date_of(first.id), None);
+        assert_eq!(index.position(second.id), Some(0));
+        assert_eq!(index.positions(&HashSet::from([first.id, second.id])), [0]);
+        assert_eq!(ids(index.in_range(None, None)), HashSet::from([second.id]));
+        assert_eq!(index.tag_counts(), [("@phone", 1)]);
+        assert!(index.by_word.keys().all(|word| word != "review"));
+    }
+
+    #[test]
+    fn replace_moves_an_entry_between_keys() {
+        let before = note("Call the bank #money", day(1));
+        let mut after = before.clone();
+        after.set_content("Email the bank #finance".to_string());
+        after.date = day(9);
+        let mut index = JournalIndex::build(std::slice::from_ref(&before));
+
+        index.replace(&before, &after);
+        assert_eq!(index.date_of(after.id), Some(day(9)));
+        assert_eq!(ids(index.in_range(Some(day(1)), Some(day(1)))), HashSet::new());
+        assert_eq!(index.tag_counts(), [("finance", 1)]);
+        assert_eq!(index.candidates(&Filter::Text(Term::new("call"))), Some(HashSet::new()));
+        assert_eq!(index.candidates(&Filter::Text(Term::new("email"))), Some(HashSet::from([after.id])));
+    }
+
+    #[test]
+    fn candidates_narrow_by_every_indexed_filter() {
+        let entries = [
+            note("Release notes for q4-plan #work", NaiveDate::from_ymd_opt(2026, 9, 30).unwrap()),
+            note("Release party #home", day(5)),
+            note("Garden layout", day(8)),
+        ];
+        let [notes, party, garden] = [entries[0].id, entries[1].id, entries[2].id];
+        let index = JournalIndex::build(&entries);
+        let candidates = |filter: Filter| index.candidates(&filter);
+
+        assert_eq!(candidates(Filter::Tag("WORK".to_string())), Some(HashSet::from([notes])));
+        assert_eq!(candidates(Filter::Date(Some(day(4)), None)), Some(HashSet::from([party, garden])));
+        assert_eq!(candidates(Filter::Date(Some(day(9)), Some(day(1)))), Some(HashSet::new()));
+        assert_eq!(candidates(Filter::Text(Term::new("relaese"))), Some(HashSet::from([notes, party])));
+        assert_eq!(candidates(Filter::Text(Term::new("q4-plan"))), Some(HashSet::from([notes])));
+        assert_eq!(candidates(Filter::Phrase(Term::new("release notes"))), Some(HashSet::from([notes])));
+        assert_eq!(
+            candidates(Filter::All(vec![Filter::Text(Term::new("release")), Filter::Date(Some(day(4)), None)])),
+            Some(HashSet::from([party])),
+        );
+        assert_eq!(candidates(Filter::Type(BulletType::Note)), None);
+        assert_eq!(candidates(Filter::Not(Box::new(Filter::Tag("work".to_string())))), None);
+    }
+}
@@ -1,12 +1,21 @@
+#[cfg(feature = "bench")]
+mod bench;
+mod cli;
 mod config;
+mod fuzzy;
+mod history;
+mod index;
+mod merge;
 mod models;
+mod query;
+mod schema;
 mod storage;
 mod ui;
 
 use anyhow::Result;
 use clap::Parser;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{Event, EventStream, KeyCode, KeyEventKind, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -14,15 +23,19 @@ use ratatui::{
     backend::CrosstermBackend,
     Terminal,
 };
+use futures::StreamExt;
 use std::io;
+use std::time::Duration;
 use ui::{
     app::{App, AppTab},
-    components::{render_tabs, render_status_bar, render_messages, render_help},
+    components::{render_tabs, render_status_bar, render_messages, render_help, render_journal_picker},
     daily::render_daily_view,
     monthly::render_monthly_view,
     future::render_future_view,
     collections::render_collections_view,
     search::render_search_view,
+    tags::render_tags_view,
+    migration::render_migration_view,
 };
 
 #[derive(Parser)]
@@ -31,9 +44,16 @@ use ui::{
 struct Cli {
     #[arg(short, long, help = "Print version information")]
     version: bool,
+
+    #[arg(long, global = true, env = "BUJO_JOURNAL", value_name = "NAME|PATH", help = "Journal to open: a name from config.toml or a directory")]
+    journal: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<cli::Commands>,
 }
 
-fn main() -> Result<()> {
+#[tokio::main]
+async fn main() -> Result<()> {
     let cli = Cli::parse();
     
     if cli.version {
@@ -41,6 +61,13 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let Some(command) = cli.command {
+        return cli::run(command, cli.journal.as_deref());
+    }
+
+    // Opened before taking over the terminal, so an encrypted journal can ask for its passphrase.
+    let mut app = App::new(cli.journal.as_deref())?;
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
@@ -48,8 +75,7 @@ fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     
-    let mut app = App::new()?;
-    let result = run_app(&mut terminal, &mut app);
+    let result = run_app(&mut terminal, &mut app).await;
     
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
@@ -62,28 +88,38 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+async fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+    let mut events = EventStream::new();
+    let mut autosave = tokio::time::interval(Duration::from_secs(1));
+
     loop {
         terminal.draw(|f| draw_ui(f, app))?;
-        
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Char('c') if key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL) => {
-                        break;
-                    }
-                    _ => {
-                        app.handle_key(key)?;
+
+        tokio::select! {
+            event = events.next() => {
+                match event {
+                    Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => {
+                        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                            app.request_quit();
+                        } else {
+                            app.handle_key(key)?;
+                        }
                         if app.should_quit {
-                            app.save()?;
                             break;
                         }
                     }
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                    None => break,
                 }
             }
+            _ = autosave.tick() => {
+                app.check_external_changes();
+                app.autosave();
+            }
         }
     }
-    
+
     Ok(())
 }
 
@@ -118,12 +154,32 @@ fn draw_ui(f: &mut ratatui::Frame, app: &App) {
         main_chunks[1]
     };
     
-    match app.current_tab {
-        AppTab::Daily => render_daily_view(f, app, content_chunks),
-        AppTab::Monthly => render_monthly_view(f, app, content_chunks),
-        AppTab::Future => render_future_view(f, app, content_chunks),
-        AppTab::Collections => render_collections_view(f, app, content_chunks),
-        AppTab::Search => render_search_view(f, app, content_chunks),
+    let content_chunks = if let Some(picked) = app.journal_picker {
+        let journals = app.config.get_config().journal_names().len() as u16;
+        let chunks = ratatui::layout::Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([
+                ratatui::layout::Constraint::Min(0),
+                ratatui::layout::Constraint::Length(journals + 2),
+            ])
+            .split(content_chunks);
+        render_journal_picker(f, app, picked, chunks[1]);
+        chunks[0]
+    } else {
+        content_chunks
+    };
+
+    if let Some(review) = &app.migration {
+        render_migration_view(f, app, review, content_chunks);
+    } else {
+        match app.current_tab {
+            AppTab::Daily => render_daily_view(f, app, content_chunks),
+            AppTab::Monthly => render_monthly_view(f, app, content_chunks),
+            AppTab::Future => render_future_view(f, app, content_chunks),
+            AppTab::Collections => render_collections_view(f, app, content_chunks),
+            AppTab::Search => render_search_view(f, app, content_chunks),
+            AppTab::Tags => render_tags_view(f, app, content_chunks),
+        }
     }
     
     render_status_bar(f, app, main_chunks[2]);
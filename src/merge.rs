@@ -0,0 +1,218 @@
+use crate::models::{Collection, Entry, Journal, SavedSearch};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Result of merging two diverged copies of the journal.
+pub struct Merge {
+    pub journal: Journal,
+    /// Items changed on both sides, where the local version was kept.
+    pub conflicts: usize,
+}
+
+/// Three-way merge of the journal by entry, collection and saved search id. `base` is the
+/// version both sides started from, `mine` holds the local edits and `theirs`
+/// is what is now on disk. Changes made on only one side are taken as-is;
+/// when both sides changed the same item, `mine` wins.
+pub fn merge_journals(base: &Journal, mine: &Journal, theirs: &Journal) -> Merge {
+    let mut conflicts = 0;
+
+    let mut entries = merge_by_id(
+        base.entries(),
+        mine.entries(),
+        theirs.entries(),
+        |entry| entry.id,
+        |_, mine, _| (mine.clone(), 1),
+        &mut conflicts,
+    );
+    entries.sort_by_key(|entry| entry.date);
+
+    let collections = merge_by_id(
+        &base.sorted_collections().into_iter().cloned().collect::<Vec<_>>(),
+        &mine.sorted_collections().into_iter().cloned().collect::<Vec<_>>(),
+        &theirs.sorted_collections().into_iter().cloned().collect::<Vec<_>>(),
+        |collection| collection.id,
+        merge_collection,
+        &mut conflicts,
+    );
+
+    let saved_searches = merge_by_id(
+        &base.sorted_saved_searches().into_iter().cloned().collect::<Vec<_>>(),
+        &mine.sorted_saved_searches().into_iter().cloned().collect::<Vec<_>>(),
+        &theirs.sorted_saved_searches().into_iter().cloned().collect::<Vec<_>>(),
+        |search| search.id,
+        |_, mine: &SavedSearch, _| (mine.clone(), 1),
+        &mut conflicts,
+    );
+
+    let settings = if mine.settings == base.settings {
+        theirs.settings.clone()
+    } else {
+        mine.settings.clone()
+    };
+
+    Merge {
+        journal: Journal::new(
+            entries,
+            collections.into_iter().map(|c| (c.id, c)).collect(),
+            saved_searches.into_iter().map(|s| (s.id, s)).collect(),
+            settings,
+        ),
+        conflicts,
+    }
+}
+
+/// Merges a collection changed on both sides field by field, merging its entries by id.
+fn merge_collection(base: Option<&Collection>, mine: &Collection, theirs: &Collection) -> (Collection, usize) {
+    let mut conflicts = 0;
+    let base_entries: &[Entry] = base.map(|b| b.entries.as_slice()).unwrap_or(&[]);
+    let entries = merge_by_id(
+        base_entries,
+        &mine.entries,
+        &theirs.entries,
+        |entry| entry.id,
+        |_, mine, _| (mine.clone(), 1),
+        &mut conflicts,
+    );
+
+    let pick = |mine_changed: bool| if mine_changed { mine } else { theirs };
+    let name = pick(base.is_none_or(|b| b.name != mine.name)).name.clone();
+    let description = pick(base.is_none_or(|b| b.description != mine.description)).description.clone();
+
+    let collection = Collection {
+        id: mine.id,
+        name,
+        description,
+        entries,
+        created_at: mine.created_at,
+    };
+    (collection, conflicts)
+}
+
+/// Merges three versions of a list of items keyed by id, keeping the order of
+/// `theirs` and appending items only `mine` added. `resolve` decides between
+/// two different versions of an item and reports how many conflicts it hit.
+fn merge_by_id<'a, T: Clone + PartialEq>(
+    base: &'a [T],
+    mine: &'a [T],
+    theirs: &'a [T],
+    id: impl Fn(&T) -> Uuid,
+    resolve: impl Fn(Option<&T>, &T, &T) -> (T, usize),
+    conflicts: &mut usize,
+) -> Vec<T> {
+    let by_id = |items: &'a [T]| -> HashMap<Uuid, &'a T> { items.iter().map(|item| (id(item), item)).collect() };
+    let (base_items, my_items, their_items) = (by_id(base), by_id(mine), by_id(theirs));
+    let mut merged = Vec::new();
+
+    for their in theirs {
+        let item_id = id(their);
+        let base_item = base_items.get(&item_id).copied();
+        match (my_items.get(&item_id).copied(), base_item) {
+            (Some(my), _) if my == their => merged.push(my.clone()),
+            (Some(my), Some(base_item)) if my == base_item => merged.push(their.clone()),
+            (Some(my), Some(base_item)) if their == base_item => merged.push(my.clone()),
+            (Some(my), base_item) => {
+                let (item, found) = resolve(base_item, my, their);
+                *conflicts += found;
+                merged.push(item);
+            }
+            // Deleted here and untouched there.
+            (None, Some(base_item)) if their == base_item => {}
+            // Deleted here but edited there: keep the edit.
+            (None, Some(_)) => {
+                *conflicts += 1;
+                merged.push(their.clone());
+            }
+            (None, None) => merged.push(their.clone()),
+        }
+    }
+
+    for my in mine {
+        let item_id = id(my);
+        if their_items.contains_key(&item_id) {
+            continue;
+        }
+        match base_items.get(&item_id) {
+            // Deleted there and untouched here.
+            Some(&base_item) if base_item == my => {}
+            // Deleted there but edited here: keep the edit.
+            Some(_) => {
+                *conflicts += 1;
+                merged.push(my.clone());
+            }
+            None => merged.push(my.clone()),
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BulletType, JournalSettings};
+    use chrono::NaiveDate;
+
+    fn journal(entries: &[&Entry]) -> Journal {
+        let entries = entries.iter().map(|&entry| entry.clone()).collect();
+        Journal::new(entries, HashMap::new(), HashMap::new(), JournalSettings::default())
+    }
+
+    fn task(content: &str) -> Entry {
+        Entry::new(content.to_string(), BulletType::Task, NaiveDate::from_ymd_opt(2026, 10, 1).unwrap())
+    }
+
+    fn edited(entry: &Entry, content: &str) -> Entry {
+        let mut entry = entry.clone();
+        entry.set_content(content.to_string());
+        entry
+    }
+
+    #[test]
+    fn items_added_on_one_side_are_kept() {
+        let shared = task("Shared");
+        let mine_only = task("Added here");
+        let theirs_only = task("Added there");
+        let base = journal(&[&shared]);
+
+        let merge = merge_journals(&base, &journal(&[&shared, &mine_only]), &journal(&[&shared, &theirs_only]));
+        let ids: Vec<Uuid> = merge.journal.entries().iter().map(|entry| entry.id).collect();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.contains(&mine_only.id) && ids.contains(&theirs_only.id));
+        assert_eq!(merge.conflicts, 0);
+    }
+
+    #[test]
+    fn an_edit_survives_a_delete_on_the_other_side() {
+        let entry = task("Call the bank");
+        let base = journal(&[&entry]);
+        let edit = edited(&entry, "Call the bank about the loan");
+
+        let merge = merge_journals(&base, &journal(&[]), &journal(&[&edit]));
+        assert_eq!(merge.journal.entries(), std::slice::from_ref(&edit));
+        assert_eq!(merge.conflicts, 1);
+
+        let merge = merge_journals(&base, &journal(&[&edit]), &journal(&[]));
+        assert_eq!(merge.journal.entries(), [edit]);
+        assert_eq!(merge.conflicts, 1);
+
+        let merge = merge_journals(&base, &journal(&[]), &journal(&[&entry]));
+        assert!(merge.journal.entries().is_empty());
+        assert_eq!(merge.conflicts, 0);
+    }
+
+    #[test]
+    fn edits_on_both_sides_keep_mine() {
+        let entry = task("Draft the plan");
+        let base = journal(&[&entry]);
+        let mine = edited(&entry, "Draft the plan today");
+        let theirs = edited(&entry, "Draft the plan tomorrow");
+
+        let merge = merge_journals(&base, &journal(&[&mine]), &journal(&[&theirs]));
+        assert_eq!(merge.journal.entries(), std::slice::from_ref(&mine));
+        assert_eq!(merge.conflicts, 1);
+
+        let merge = merge_journals(&base, &journal(&[&mine]), &journal(&[&mine]));
+        assert_eq!(merge.journal.entries(), [mine]);
+        assert_eq!(merge.conflicts, 0);
+    }
+}
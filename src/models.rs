@@ -1,6 +1,11 @@
-use chrono::{DateTime, Local, NaiveDate, Datelike};
+use crate::index::JournalIndex;
+use crate::query::{Query, Relevance};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Datelike, Local, Months, NaiveDate};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -10,6 +15,8 @@ pub enum BulletType {
     Note,
 }
 
+pub const MAX_PRIORITY: u8 = 5;
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TaskStatus {
     Incomplete,
@@ -19,7 +26,25 @@ pub enum TaskStatus {
     Irrelevant,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl TaskStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaskStatus::Incomplete => "incomplete",
+            TaskStatus::Complete => "complete",
+            TaskStatus::Migrated => "migrated",
+            TaskStatus::Scheduled => "scheduled",
+            TaskStatus::Irrelevant => "irrelevant",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub status: TaskStatus,
+    pub changed_at: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Entry {
     pub id: Uuid,
     pub content: String,
@@ -27,15 +52,23 @@ pub struct Entry {
     pub status: Option<TaskStatus>,
     pub created_at: DateTime<Local>,
     pub date: NaiveDate,
+    /// Planned for the month of `date`, which is then its first day, rather
+    /// than for that day: a Future Log item not yet given a day.
+    #[serde(default)]
+    pub whole_month: bool,
     pub tags: Vec<String>,
     pub priority: Option<u8>,
+    /// Marked with the `!` signifier as an idea worth coming back to.
+    #[serde(default)]
+    pub inspiration: bool,
+    #[serde(default)]
+    pub status_history: Vec<StatusChange>,
 }
 
 impl Entry {
     pub fn new(content: String, bullet_type: BulletType, date: NaiveDate) -> Self {
         Self {
             id: Uuid::new_v4(),
-            content,
             bullet_type,
             status: match bullet_type {
                 BulletType::Task => Some(TaskStatus::Incomplete),
@@ -43,8 +76,58 @@ impl Entry {
             },
             created_at: Local::now(),
             date,
-            tags: Vec::new(),
+            whole_month: false,
+            tags: inline_tags(&content),
+            content,
             priority: None,
+            inspiration: false,
+            status_history: Vec::new(),
+        }
+    }
+
+    /// Creates an entry from typed input, taking leading signifiers off the content.
+    pub fn from_input(input: &str, bullet_type: BulletType, date: NaiveDate) -> Self {
+        let (signifiers, content) = parse_signifiers(input);
+        let mut entry = Self::new(content.to_string(), bullet_type, date);
+        signifiers.apply(&mut entry);
+        entry
+    }
+
+    /// Replaces the content, keeping `tags` in step with the `#tags` and
+    /// `@contexts` written in it. Tags added by other means are left alone.
+    pub fn set_content(&mut self, content: String) {
+        let previous = inline_tags(&self.content);
+        self.tags.retain(|tag| !previous.contains(tag));
+        self.content = content;
+        self.add_tags(inline_tags(&self.content));
+    }
+
+    /// Plans the entry for a day, or for the whole month `date` falls in.
+    pub fn set_date(&mut self, date: NaiveDate, whole_month: bool) {
+        self.date = if whole_month { date.with_day(1).unwrap_or(date) } else { date };
+        self.whole_month = whole_month;
+    }
+
+    /// The last day the entry is planned for: its date, or the end of its month.
+    pub fn last_day(&self) -> NaiveDate {
+        if self.whole_month {
+            last_day_of_month(self.date)
+        } else {
+            self.date
+        }
+    }
+
+    /// `2026-12-14`, or `2026-12` for a whole-month entry.
+    pub fn date_label(&self) -> String {
+        let format = if self.whole_month { "%Y-%m" } else { "%Y-%m-%d" };
+        self.date.format(format).to_string()
+    }
+
+    pub fn add_tags(&mut self, tags: impl IntoIterator<Item = String>) {
+        for tag in tags {
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
         }
     }
 
@@ -63,18 +146,54 @@ impl Entry {
         }
     }
 
-    pub fn toggle_complete(&mut self) {
-        if self.bullet_type == BulletType::Task {
-            self.status = match self.status {
-                Some(TaskStatus::Incomplete) => Some(TaskStatus::Complete),
-                Some(TaskStatus::Complete) => Some(TaskStatus::Incomplete),
-                other => other,
-            };
+    /// Changes the bullet type, giving new tasks an incomplete status and clearing it otherwise.
+    pub fn set_bullet_type(&mut self, bullet_type: BulletType) {
+        self.bullet_type = bullet_type;
+        self.status = match bullet_type {
+            BulletType::Task => self.status.or(Some(TaskStatus::Incomplete)),
+            _ => None,
+        };
+    }
+
+    pub fn raise_priority(&mut self) {
+        self.priority = Some(self.priority.map_or(1, |p| (p + 1).min(MAX_PRIORITY)));
+    }
+
+    pub fn lower_priority(&mut self) {
+        self.priority = match self.priority {
+            Some(p) if p > 1 => Some(p - 1),
+            _ => None,
+        };
+    }
+
+    /// Moves a task to `status`, recording the change in its history.
+    pub fn set_status(&mut self, status: TaskStatus) -> Result<()> {
+        if self.bullet_type != BulletType::Task {
+            bail!("Only tasks have a status");
         }
+        if self.status == Some(status) {
+            return Ok(());
+        }
+        self.status = Some(status);
+        self.status_history.push(StatusChange {
+            status,
+            changed_at: Local::now(),
+        });
+        Ok(())
+    }
+
+    pub fn toggle_complete(&mut self) {
+        let status = match self.status {
+            Some(TaskStatus::Incomplete) => TaskStatus::Complete,
+            Some(TaskStatus::Complete) => TaskStatus::Incomplete,
+            _ => return,
+        };
+        // Only tasks carry a status, so this cannot fail.
+        let _ = self.set_status(status);
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Collection {
     pub id: Uuid,
     pub name: String,
@@ -93,16 +212,51 @@ impl Collection {
             created_at: Local::now(),
         }
     }
+
+    pub fn add_entry(&mut self, entry: Entry) {
+        self.entries.push(entry);
+    }
+
+    pub fn remove_entry(&mut self, id: Uuid) -> Option<Entry> {
+        let index = self.entries.iter().position(|entry| entry.id == id)?;
+        Some(self.entries.remove(index))
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A named search query, listed with the collections and re-run whenever it is shown.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: Uuid,
+    pub name: String,
+    pub query: String,
+    pub created_at: DateTime<Local>,
+}
+
+impl SavedSearch {
+    pub fn new(name: String, query: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            query,
+            created_at: Local::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Journal {
-    pub entries: Vec<Entry>,
+    /// The daily log, ordered by date. Changed only through `Journal`, which
+    /// keeps `index` in step.
+    entries: Vec<Entry>,
     pub collections: HashMap<Uuid, Collection>,
+    pub saved_searches: HashMap<Uuid, SavedSearch>,
     pub settings: JournalSettings,
+    /// Built on first lookup, then updated with each change to `entries`.
+    #[serde(skip)]
+    index: OnceCell<JournalIndex>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct JournalSettings {
     pub week_starts_monday: bool,
     pub show_completed_tasks: bool,
@@ -119,53 +273,306 @@ impl Default for JournalSettings {
     }
 }
 
-impl Default for Journal {
-    fn default() -> Self {
-        Self {
-            entries: Vec::new(),
-            collections: HashMap::new(),
-            settings: JournalSettings::default(),
+/// Bullet journal signifiers typed in front of an entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Signifiers {
+    pub priority: Option<u8>,
+    pub inspiration: bool,
+}
+
+impl Signifiers {
+    /// Sets what the signifiers mark on the entry; absent ones leave it unchanged.
+    pub fn apply(self, entry: &mut Entry) {
+        if self.priority.is_some() {
+            entry.priority = self.priority;
+        }
+        if self.inspiration {
+            entry.inspiration = true;
         }
     }
 }
 
+/// Splits leading signifiers off input such as `** ! Call the bank`: each `*`
+/// raises the priority by one and `!` marks inspiration. They only count as
+/// signifiers when followed by a space, so `*emphasis*` is left alone.
+pub fn parse_signifiers(input: &str) -> (Signifiers, &str) {
+    let trimmed = input.trim_start();
+    let prefix_len = trimmed
+        .find(|c: char| !matches!(c, '*' | '!') && !c.is_whitespace())
+        .unwrap_or(trimmed.len());
+    let prefix = &trimmed[..prefix_len];
+    let rest = &trimmed[prefix_len..];
+    if prefix.is_empty() || !prefix.ends_with(char::is_whitespace) || rest.is_empty() {
+        return (Signifiers::default(), input);
+    }
+
+    let stars = prefix.chars().filter(|c| *c == '*').count();
+    let signifiers = Signifiers {
+        priority: (stars > 0).then(|| stars.min(MAX_PRIORITY as usize) as u8),
+        inspiration: prefix.contains('!'),
+    };
+    (signifiers, rest)
+}
+
+/// Byte ranges of the `#tag` and `@context` tokens in entry content, sigil included.
+/// A token starts a word, begins with a letter and runs over letters, digits, `-`, `_` and `/`.
+pub fn inline_tag_ranges(content: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut previous = None;
+    let mut chars = content.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let starts_word = previous.is_none_or(|p: char| p.is_whitespace() || p == '(');
+        previous = Some(c);
+        if !(starts_word && (c == '#' || c == '@')) {
+            continue;
+        }
+        if !chars.peek().is_some_and(|(_, next)| next.is_alphabetic()) {
+            continue;
+        }
+        let mut end = start + 1;
+        while let Some(&(index, next)) = chars.peek() {
+            if !(next.is_alphanumeric() || matches!(next, '-' | '_' | '/')) {
+                break;
+            }
+            end = index + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+        let token = content[start..end].trim_end_matches(['-', '_', '/']);
+        ranges.push(start..start + token.len());
+    }
+    ranges
+}
+
+/// Reads `YYYY-MM-DD`, or `YYYY-MM` for a whole month, as a date and whether
+/// it means the whole month, which is then dated its first day.
+pub fn parse_entry_date(input: &str) -> Option<(NaiveDate, bool)> {
+    let input = input.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Some((date, false));
+    }
+    NaiveDate::parse_from_str(&format!("{}-01", input), "%Y-%m-%d")
+        .ok()
+        .map(|first| (first, true))
+}
+
+pub fn last_day_of_month(date: NaiveDate) -> NaiveDate {
+    let first = date.with_day(1).unwrap_or(date);
+    first
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .unwrap_or(date)
+}
+
+/// A tag as stored, however it was given: `#Work` becomes `work` and
+/// `@Home` becomes `@home`, so contexts never collide with tags.
+pub fn normalize_tag(tag: &str) -> String {
+    let tag = tag.trim();
+    tag.strip_prefix('#').unwrap_or(tag).to_lowercase()
+}
+
+/// The tags written inline in entry content, as `normalize_tag` stores them.
+pub fn inline_tags(content: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for range in inline_tag_ranges(content) {
+        let tag = normalize_tag(&content[range]);
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
 impl Journal {
+    /// A journal of the given entries, which must be ordered by date.
+    pub fn new(
+        entries: Vec<Entry>,
+        collections: HashMap<Uuid, Collection>,
+        saved_searches: HashMap<Uuid, SavedSearch>,
+        settings: JournalSettings,
+    ) -> Self {
+        Self { entries, collections, saved_searches, settings, index: OnceCell::new() }
+    }
+
+    /// The daily log, ordered by date.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// The daily log for changes made in bulk, such as loading or clearing it.
+    /// The index is dropped and rebuilt on the next lookup.
+    pub fn entries_mut(&mut self) -> &mut Vec<Entry> {
+        self.index.take();
+        &mut self.entries
+    }
+
+    fn index(&self) -> &JournalIndex {
+        self.index.get_or_init(|| JournalIndex::build(&self.entries))
+    }
+
+    fn position(&self, id: Uuid) -> Option<usize> {
+        self.index().position(id)
+    }
+
+    /// The log entries with the given ids, in log order.
+    fn resolve(&self, ids: &HashSet<Uuid>) -> Vec<&Entry> {
+        self.index().positions(ids).into_iter().map(|position| &self.entries[position]).collect()
+    }
+
+    /// Entries logged for the day; whole-month entries belong to the monthly log instead.
     pub fn entries_for_date(&self, date: NaiveDate) -> Vec<&Entry> {
-        self.entries
-            .iter()
-            .filter(|entry| entry.date == date)
-            .collect()
+        let mut entries = self.entries_in_range(Some(date), Some(date));
+        entries.retain(|entry| !entry.whole_month);
+        entries
     }
 
     pub fn entries_for_month(&self, year: i32, month: u32) -> Vec<&Entry> {
-        self.entries
-            .iter()
-            .filter(|entry| entry.date.year() == year && entry.date.month() == month)
-            .collect()
+        let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+            return Vec::new();
+        };
+        self.entries_in_range(Some(first), Some(last_day_of_month(first)))
+    }
+
+    pub fn entries_in_range(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Vec<&Entry> {
+        self.resolve(&self.index().in_range(from, to).copied().collect())
     }
 
     pub fn add_entry(&mut self, entry: Entry) {
-        self.entries.push(entry);
-        self.entries.sort_by(|a, b| a.date.cmp(&b.date));
+        let position = self.entries.partition_point(|other| other.date <= entry.date);
+        self.insert_entry(position, entry);
+    }
+
+    /// Puts an entry back at a position in the log, as when undoing its deletion.
+    /// The position is kept among the entries of the same date, since the log
+    /// may have changed underneath it since.
+    pub fn insert_entry(&mut self, position: usize, entry: Entry) {
+        let first = self.entries.partition_point(|other| other.date < entry.date);
+        let last = self.entries.partition_point(|other| other.date <= entry.date);
+        let position = position.clamp(first, last);
+        if let Some(index) = self.index.get_mut() {
+            index.insert(position, &entry);
+        }
+        self.entries.insert(position, entry);
     }
 
     pub fn remove_entry(&mut self, id: Uuid) {
-        self.entries.retain(|entry| entry.id != id);
+        self.remove_logged(id);
+        for collection in self.collections.values_mut() {
+            collection.remove_entry(id);
+        }
     }
 
+    /// Takes an entry out of the log, leaving collections alone.
+    fn remove_logged(&mut self, id: Uuid) -> Option<Entry> {
+        let position = self.position(id)?;
+        let entry = self.entries.remove(position);
+        if let Some(index) = self.index.get_mut() {
+            index.remove(&entry);
+        }
+        Some(entry)
+    }
+
+    /// Replaces the entry with the same id, keeping the log ordered by date.
+    /// An entry moved to another day goes where a stable sort would put it.
+    pub fn replace_entry(&mut self, entry: Entry) -> bool {
+        if let Some(position) = self.position(entry.id) {
+            let old_date = self.entries[position].date;
+            if entry.date == old_date {
+                if let Some(index) = self.index.get_mut() {
+                    index.replace(&self.entries[position], &entry);
+                }
+                self.entries[position] = entry;
+            } else {
+                self.remove_logged(entry.id);
+                let position = if entry.date > old_date {
+                    self.entries.partition_point(|other| other.date < entry.date)
+                } else {
+                    self.entries.partition_point(|other| other.date <= entry.date)
+                };
+                self.insert_entry(position, entry);
+            }
+            return true;
+        }
+        let existing = self.collections
+            .values_mut()
+            .flat_map(|collection| collection.entries.iter_mut())
+            .find(|existing| existing.id == entry.id);
+        match existing {
+            Some(existing) => {
+                *existing = entry;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Looks up an entry in the logs first, then inside collections.
+    pub fn get_entry(&self, id: Uuid) -> Option<&Entry> {
+        if let Some(position) = self.position(id) {
+            return self.entries.get(position);
+        }
+        self.collections
+            .values()
+            .flat_map(|collection| collection.entries.iter())
+            .find(|entry| entry.id == id)
+    }
+
+    /// Mutable access to an entry for edits in place. The index is dropped since
+    /// the content or tags may change; moving an entry to another day should go
+    /// through `replace_entry` so the log stays ordered by date.
+    #[allow(dead_code)]
     pub fn get_entry_mut(&mut self, id: Uuid) -> Option<&mut Entry> {
-        self.entries.iter_mut().find(|entry| entry.id == id)
+        if let Some(position) = self.position(id) {
+            self.index.take();
+            return self.entries.get_mut(position);
+        }
+        self.collections
+            .values_mut()
+            .flat_map(|collection| collection.entries.iter_mut())
+            .find(|entry| entry.id == id)
     }
 
-    pub fn search_entries(&self, query: &str) -> Vec<&Entry> {
-        let query = query.to_lowercase();
-        self.entries
-            .iter()
-            .filter(|entry| {
-                entry.content.to_lowercase().contains(&query)
-                    || entry.tags.iter().any(|tag| tag.to_lowercase().contains(&query))
-            })
-            .collect()
+    /// Collections in the order they were created.
+    pub fn sorted_collections(&self) -> Vec<&Collection> {
+        let mut collections: Vec<&Collection> = self.collections.values().collect();
+        collections.sort_by_key(|collection| collection.created_at);
+        collections
+    }
+
+    pub fn sorted_saved_searches(&self) -> Vec<&SavedSearch> {
+        let mut searches: Vec<&SavedSearch> = self.saved_searches.values().collect();
+        searches.sort_by_key(|search| search.created_at);
+        searches
+    }
+
+    pub fn saved_search_named(&self, name: &str) -> Option<&SavedSearch> {
+        self.saved_searches.values().find(|search| search.name == name)
+    }
+
+    /// Daily log entries matching the query, most relevant first.
+    pub fn search_entries(&self, query: &Query) -> Vec<(&Entry, Relevance)> {
+        let today = Local::now().date_naive();
+        let candidates = match self.index().candidates(&query.filter) {
+            Some(ids) => self.resolve(&ids),
+            None => self.entries.iter().collect(),
+        };
+        let mut found: Vec<(&Entry, Relevance)> = candidates
+            .into_iter()
+            .filter_map(|entry| Some((entry, query.rank(entry, today)?)))
+            .collect();
+        found.sort_by(|(a, a_relevance), (b, b_relevance)| {
+            b_relevance.score.cmp(&a_relevance.score).then(b.date.cmp(&a.date))
+        });
+        found
+    }
+
+    /// Every tag used in the daily log with the number of entries carrying it, by name.
+    pub fn tag_counts(&self) -> Vec<(&str, usize)> {
+        self.index().tag_counts()
+    }
+
+    pub fn entries_with_tag(&self, tag: &str) -> Vec<&Entry> {
+        self.resolve(&self.index().with_tag(tag).copied().collect())
     }
 
     pub fn incomplete_tasks(&self) -> Vec<&Entry> {
@@ -178,22 +585,182 @@ impl Journal {
             .collect()
     }
 
-    pub fn migrate_incomplete_tasks(&mut self, from_date: NaiveDate, to_date: NaiveDate) {
-        let mut new_entries = Vec::new();
-        for entry in &mut self.entries {
-            if entry.date == from_date
-                && entry.bullet_type == BulletType::Task
-                && entry.status == Some(TaskStatus::Incomplete)
-            {
-                entry.status = Some(TaskStatus::Migrated);
-                let mut new_entry = entry.clone();
-                new_entry.id = Uuid::new_v4();
-                new_entry.date = to_date;
-                new_entry.status = Some(TaskStatus::Incomplete);
-                new_entries.push(new_entry);
-            }
+    /// Incomplete tasks logged on days before `today`, or for months before
+    /// this one, oldest first.
+    pub fn overdue_tasks(&self, today: NaiveDate) -> Vec<&Entry> {
+        self.incomplete_tasks()
+            .into_iter()
+            .filter(|entry| entry.last_day() < today)
+            .collect()
+    }
+
+    /// Marks an incomplete task as migrated (`>`) and carries a copy forward to `to_date`.
+    pub fn migrate_entry(&mut self, id: Uuid, to_date: NaiveDate) -> Option<Uuid> {
+        let mut new_entry = self.forward_task(id, TaskStatus::Migrated)?;
+        new_entry.set_date(to_date, false);
+        let new_id = new_entry.id;
+        self.add_entry(new_entry);
+        Some(new_id)
+    }
+
+    /// Marks an incomplete task as scheduled (`<`) and places a copy in the
+    /// future log on `date`, or in its month as a whole.
+    pub fn schedule_entry(&mut self, id: Uuid, date: NaiveDate, whole_month: bool) -> Option<Uuid> {
+        let mut new_entry = self.forward_task(id, TaskStatus::Scheduled)?;
+        new_entry.set_date(date, whole_month);
+        let new_id = new_entry.id;
+        self.add_entry(new_entry);
+        Some(new_id)
+    }
+
+    /// Marks an incomplete task as migrated (`>`) and carries a copy into a collection.
+    pub fn migrate_entry_to_collection(&mut self, id: Uuid, collection_id: Uuid) -> Option<Uuid> {
+        if !self.collections.contains_key(&collection_id) {
+            return None;
         }
-        self.entries.extend(new_entries);
-        self.entries.sort_by(|a, b| a.date.cmp(&b.date));
+        let new_entry = self.forward_task(id, TaskStatus::Migrated)?;
+        let new_id = new_entry.id;
+        self.collections.get_mut(&collection_id)?.add_entry(new_entry);
+        Some(new_id)
+    }
+
+    /// Sets the status of an incomplete task in the logs and returns a fresh incomplete copy of it.
+    fn forward_task(&mut self, id: Uuid, status: TaskStatus) -> Option<Entry> {
+        // Only the status changes, which the index does not cover.
+        let position = self.position(id)?;
+        let entry = &mut self.entries[position];
+        if entry.bullet_type != BulletType::Task || entry.status != Some(TaskStatus::Incomplete) {
+            return None;
+        }
+        entry.set_status(status).ok()?;
+        let mut new_entry = entry.clone();
+        new_entry.id = Uuid::new_v4();
+        new_entry.status = Some(TaskStatus::Incomplete);
+        new_entry.status_history.clear();
+        Some(new_entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_normalized_however_they_are_given() {
+        assert_eq!(normalize_tag("#Work"), "work");
+        assert_eq!(normalize_tag(" @Home "), "@home");
+        assert_eq!(normalize_tag("q4-plan"), "q4-plan");
+        assert_eq!(normalize_tag("#"), "");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn inline_tags_start_words_and_begin_with_a_letter() {
+        let tokens = |content| -> Vec<&str> {
+            inline_tag_ranges(content).into_iter().map(|range| &content[range]).collect()
+        };
+        let content = "Call (@Phone) about #Q4-plan/draft, #2nd issue#3 and email@example.com #work-";
+        assert_eq!(tokens(content), ["@Phone", "#Q4-plan/draft", "#work"]);
+        assert_eq!(inline_tags(content), ["@phone", "q4-plan/draft", "work"]);
+
+        assert_eq!(tokens("Café #Thé, s'il vous plaît"), ["#Thé"]);
+        assert_eq!(inline_tags("#Work then #work again"), ["work"]);
+        assert!(inline_tags("# heading and @ alone").is_empty());
+    }
+
+    #[test]
+    fn leading_signifiers_set_priority_and_inspiration() {
+        let parse = |input| {
+            let (signifiers, rest) = parse_signifiers(input);
+            (signifiers.priority, signifiers.inspiration, rest)
+        };
+        assert_eq!(parse("** Pay rent"), (Some(2), false, "Pay rent"));
+        assert_eq!(parse("! Garden layout"), (None, true, "Garden layout"));
+        assert_eq!(parse("*! Pitch the redesign"), (Some(1), true, "Pitch the redesign"));
+        assert_eq!(parse("* * ! Spaced out"), (Some(2), true, "Spaced out"));
+        assert_eq!(parse("******* Everything"), (Some(MAX_PRIORITY), false, "Everything"));
+        // Signifiers need a space after them and something to mark.
+        assert_eq!(parse("*emphasis* stays"), (None, false, "*emphasis* stays"));
+        assert_eq!(parse("**"), (None, false, "**"));
+        assert_eq!(parse("Plain"), (None, false, "Plain"));
+    }
+
+    #[test]
+    fn priority_steps_stay_between_none_and_the_maximum() {
+        let mut entry = Entry::new("Pay rent".to_string(), BulletType::Task, NaiveDate::from_ymd_opt(2026, 10, 1).unwrap());
+        entry.raise_priority();
+        assert_eq!(entry.priority, Some(1));
+        for _ in 0..MAX_PRIORITY + 2 {
+            entry.raise_priority();
+        }
+        assert_eq!(entry.priority, Some(MAX_PRIORITY));
+
+        entry.priority = Some(2);
+        entry.lower_priority();
+        assert_eq!(entry.priority, Some(1));
+        entry.lower_priority();
+        assert_eq!(entry.priority, None);
+        entry.lower_priority();
+        assert_eq!(entry.priority, None);
+    }
+
+    #[test]
+    fn edits_made_in_place_show_up_in_later_lookups() {
+        let mut journal = Journal::default();
+        let entry = Entry::new("Call the bank".to_string(), BulletType::Task, NaiveDate::from_ymd_opt(2026, 10, 1).unwrap());
+        let id = entry.id;
+        journal.add_entry(entry);
+        assert!(journal.entries_with_tag("money").is_empty());
+
+        let entry = journal.get_entry_mut(id).unwrap();
+        entry.set_content("Call the bank #money".to_string());
+        entry.raise_priority();
+        assert_eq!(journal.entries_with_tag("money").len(), 1);
+        assert_eq!(journal.get_entry(id).and_then(|entry| entry.priority), Some(1));
+        assert!(journal.get_entry_mut(Uuid::new_v4()).is_none());
+    }
+
+    #[test]
+    fn status_changes_are_recorded_in_order() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let mut task = Entry::new("File taxes".to_string(), BulletType::Task, date);
+        let statuses = |task: &Entry| -> Vec<TaskStatus> {
+            task.status_history.iter().map(|change| change.status).collect()
+        };
+        assert!(task.status_history.is_empty());
+
+        task.set_status(TaskStatus::Complete).unwrap();
+        task.set_status(TaskStatus::Complete).unwrap();
+        assert_eq!(statuses(&task), [TaskStatus::Complete]);
+
+        task.toggle_complete();
+        task.toggle_complete();
+        assert_eq!(task.status, Some(TaskStatus::Complete));
+        assert_eq!(statuses(&task), [TaskStatus::Complete, TaskStatus::Incomplete, TaskStatus::Complete]);
+        assert!(task.status_history.windows(2).all(|pair| pair[0].changed_at <= pair[1].changed_at));
+
+        task.set_status(TaskStatus::Scheduled).unwrap();
+        task.toggle_complete();
+        assert_eq!(task.status, Some(TaskStatus::Scheduled));
+        task.set_status(TaskStatus::Irrelevant).unwrap();
+        assert_eq!(task.symbol(), "✗");
+        assert_eq!(statuses(&task).len(), 5);
+    }
+
+    #[test]
+    fn only_tasks_carry_a_status() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let mut note = Entry::new("Idea".to_string(), BulletType::Note, date);
+        assert!(note.set_status(TaskStatus::Complete).is_err());
+        note.toggle_complete();
+        assert_eq!(note.status, None);
+        assert!(note.status_history.is_empty());
+
+        note.set_bullet_type(BulletType::Task);
+        assert_eq!(note.status, Some(TaskStatus::Incomplete));
+        note.set_status(TaskStatus::Migrated).unwrap();
+        note.set_bullet_type(BulletType::Task);
+        assert_eq!(note.status, Some(TaskStatus::Migrated));
+        note.set_bullet_type(BulletType::Event);
+        assert_eq!(note.status, None);
+    }
+}
@@ -0,0 +1,457 @@
+//! The search query language shared by the Search tab and `bujo search`.
+//!
+//! A query is a list of terms that must all match:
+//!
+//! ```text
+//! type:task status:incomplete tag:work date:2026-09..2026-10 priority:>2 "exact phrase" -exclude
+//! ```
+//!
+//! Dates may be relative, as in `date:..today-3`, so saved searches stay current.
+//!
+//! Bare words match entry content and tags fuzzily, quoted phrases exactly,
+//! both ignoring case. A leading `-` negates any term.
+
+use crate::fuzzy::{Term, TextMatch};
+use crate::models::{inline_tag_ranges, inline_tags, normalize_tag, BulletType, Entry, TaskStatus};
+use anyhow::{bail, Result};
+use chrono::{Local, Months, NaiveDate, TimeDelta};
+use std::ops::Range;
+
+const FILTER_NAMES: &str = "type, status, tag, date or priority";
+
+/// Added to a term's score when it matched a tag rather than only content.
+const TAG_BONUS: i64 = 15;
+/// Added for an entry dated today, falling off with each month away from it.
+const RECENCY_BONUS: i64 = 30;
+
+/// A parsed query. An empty query matches every entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub filter: Filter,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// Matches when every filter matches.
+    All(Vec<Filter>),
+    Not(Box<Filter>),
+    Type(BulletType),
+    Status(TaskStatus),
+    /// A tag as stored, so `work` for `#work` and `@home` for a context.
+    Tag(String),
+    /// Entries dated within the inclusive range; either end may be open.
+    Date(Option<NaiveDate>, Option<NaiveDate>),
+    /// Entries without a priority count as priority 0.
+    Priority(Comparison, u8),
+    /// A word found in the content or a tag, allowing for typos.
+    Text(Term),
+    /// Text found exactly in the content or a tag.
+    Phrase(Term),
+}
+
+/// How relevant a matching entry is, and the bytes of its content to highlight.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Relevance {
+    pub score: i64,
+    pub highlights: Vec<Range<usize>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn holds(self, value: u8, bound: u8) -> bool {
+        match self {
+            Comparison::Less => value < bound,
+            Comparison::LessOrEqual => value <= bound,
+            Comparison::Equal => value == bound,
+            Comparison::GreaterOrEqual => value >= bound,
+            Comparison::Greater => value > bound,
+        }
+    }
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self> {
+        let filters = tokenize(input)?
+            .into_iter()
+            .map(parse_term)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { filter: Filter::All(filters) })
+    }
+
+    /// Scores an entry if it matches, favouring entries dated close to `today`.
+    pub fn rank(&self, entry: &Entry, today: NaiveDate) -> Option<Relevance> {
+        let mut relevance = Relevance::default();
+        if !self.filter.evaluate(entry, &mut relevance) {
+            return None;
+        }
+        let months_away = (entry.date - today).num_days().abs() / 30;
+        relevance.score += RECENCY_BONUS / (1 + months_away);
+        Some(relevance)
+    }
+}
+
+impl Filter {
+    /// Whether the entry matches, adding what matched to `relevance`.
+    fn evaluate(&self, entry: &Entry, relevance: &mut Relevance) -> bool {
+        match self {
+            Filter::All(filters) => filters.iter().all(|filter| filter.evaluate(entry, relevance)),
+            Filter::Not(filter) => !filter.evaluate(entry, &mut Relevance::default()),
+            Filter::Type(bullet_type) => entry.bullet_type == *bullet_type,
+            Filter::Status(status) => entry.status == Some(*status),
+            Filter::Tag(tag) => {
+                if !entry.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    return false;
+                }
+                relevance.score += TAG_BONUS;
+                relevance.highlights.extend(
+                    inline_tag_ranges(&entry.content)
+                        .into_iter()
+                        .filter(|range| inline_tags(&entry.content[range.clone()]).first() == Some(tag)),
+                );
+                true
+            }
+            // A whole-month entry covers every day of its month.
+            Filter::Date(from, to) => {
+                from.is_none_or(|from| entry.last_day() >= from) && to.is_none_or(|to| entry.date <= to)
+            }
+            Filter::Priority(comparison, bound) => comparison.holds(entry.priority.unwrap_or(0), *bound),
+            Filter::Text(term) => match_text(entry, relevance, |text| term.find(text)),
+            Filter::Phrase(phrase) => match_text(entry, relevance, |text| phrase.find_exact(text)),
+        }
+    }
+}
+
+/// Matches a term against the content and tags, scoring the best of each.
+fn match_text(entry: &Entry, relevance: &mut Relevance, find: impl Fn(&str) -> Option<TextMatch>) -> bool {
+    let content = find(&entry.content);
+    let tag = entry.tags.iter().filter_map(|tag| find(tag)).map(|found| found.score).max();
+    if content.is_none() && tag.is_none() {
+        return false;
+    }
+    if let Some(content) = content {
+        relevance.score += content.score;
+        relevance.highlights.extend(content.ranges);
+    }
+    if let Some(score) = tag {
+        relevance.score += score / 2 + TAG_BONUS;
+    }
+    true
+}
+
+/// One whitespace-separated piece of the query, with its quoting undone.
+struct Token {
+    negated: bool,
+    /// The filter name before a `:`, if any.
+    key: Option<String>,
+    value: String,
+    quoted: bool,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return Ok(tokens);
+        }
+
+        let negated = chars.next_if_eq(&'-').is_some();
+        let mut key = None;
+        let mut value = String::new();
+        let mut quoted = false;
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+            match c {
+                '"' => {
+                    quoted = true;
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some(c) => value.push(c),
+                            None => bail!("Missing closing quote after \"{}", value),
+                        }
+                    }
+                }
+                ':' if key.is_none() && !quoted => key = Some(std::mem::take(&mut value)),
+                c => value.push(c),
+            }
+        }
+
+        if value.is_empty() && !quoted {
+            match key {
+                Some(key) => bail!("'{}:' needs a value", key),
+                None if negated => bail!("'-' needs a term to exclude"),
+                None => {}
+            }
+        }
+        tokens.push(Token { negated, key, value, quoted });
+    }
+}
+
+fn parse_term(token: Token) -> Result<Filter> {
+    let filter = match token.key.as_deref() {
+        // Excluding a word only drops entries that contain it as written.
+        None if token.quoted || token.negated => Filter::Phrase(Term::new(&token.value)),
+        None => Filter::Text(Term::new(&token.value)),
+        Some("type") => Filter::Type(parse_type(&token.value)?),
+        Some("status") => Filter::Status(parse_status(&token.value)?),
+        Some("tag") => Filter::Tag(normalize_tag(&token.value)),
+        Some("date") => parse_date_range(&token.value)?,
+        Some("priority") => parse_priority(&token.value)?,
+        // Text with a colon in it, such as a time, is searched for as written.
+        Some(key) if token.quoted || key.chars().all(|c| c.is_ascii_digit()) => {
+            Filter::Phrase(Term::new(&format!("{}:{}", key, token.value)))
+        }
+        Some(key) => bail!("Unknown filter '{}:'; use {}", key, FILTER_NAMES),
+    };
+    Ok(if token.negated { Filter::Not(Box::new(filter)) } else { filter })
+}
+
+fn parse_type(value: &str) -> Result<BulletType> {
+    match value.to_lowercase().as_str() {
+        "task" => Ok(BulletType::Task),
+        "event" => Ok(BulletType::Event),
+        "note" => Ok(BulletType::Note),
+        _ => bail!("Unknown type '{}'; use task, event or note", value),
+    }
+}
+
+fn parse_status(value: &str) -> Result<TaskStatus> {
+    let statuses = [
+        TaskStatus::Incomplete,
+        TaskStatus::Complete,
+        TaskStatus::Migrated,
+        TaskStatus::Scheduled,
+        TaskStatus::Irrelevant,
+    ];
+    let value = value.to_lowercase();
+    match statuses.into_iter().find(|status| status.label() == value) {
+        Some(status) => Ok(status),
+        None => bail!(
+            "Unknown status '{}'; use incomplete, complete, migrated, scheduled or irrelevant",
+            value
+        ),
+    }
+}
+
+/// `date:2026-09-14`, `date:2026-09`, `date:2026`, `date:today`, a day relative
+/// to today such as `date:today-3`, or a range `from..to` of those, where
+/// either end may be left out.
+fn parse_date_range(value: &str) -> Result<Filter> {
+    let Some((from, to)) = value.split_once("..") else {
+        let (first, last) = parse_period(value)?;
+        return Ok(Filter::Date(Some(first), Some(last)));
+    };
+    if from.is_empty() && to.is_empty() {
+        bail!("Date range '..' needs at least one end");
+    }
+    let from = if from.is_empty() { None } else { Some(parse_period(from)?.0) };
+    let to = if to.is_empty() { None } else { Some(parse_period(to)?.1) };
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            bail!("Date range '{}' ends before it starts", value);
+        }
+    }
+    Ok(Filter::Date(from, to))
+}
+
+/// The first and last day of a day, month or year.
+fn parse_period(value: &str) -> Result<(NaiveDate, NaiveDate)> {
+    if let Some(offset) = value.strip_prefix("today") {
+        let today = Local::now().date_naive();
+        let day = match offset {
+            "" => Some(today),
+            _ => offset
+                .parse::<i64>()
+                .ok()
+                .filter(|_| offset.starts_with(['+', '-']))
+                .and_then(TimeDelta::try_days)
+                .and_then(|days| today.checked_add_signed(days)),
+        };
+        if let Some(day) = day {
+            return Ok((day, day));
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok((date, date));
+    }
+    if let Ok(first) = NaiveDate::parse_from_str(&format!("{}-01", value), "%Y-%m-%d") {
+        let next_month = first.checked_add_months(Months::new(1)).unwrap_or(first);
+        return Ok((first, next_month.pred_opt().unwrap_or(first)));
+    }
+    if let Ok(year) = value.parse::<i32>() {
+        if let (Some(first), Some(last)) = (NaiveDate::from_ymd_opt(year, 1, 1), NaiveDate::from_ymd_opt(year, 12, 31)) {
+            return Ok((first, last));
+        }
+    }
+    bail!("Invalid date '{}'; use YYYY-MM-DD, YYYY-MM, YYYY, today or today-N", value)
+}
+
+/// `priority:3`, `priority:>2`, `priority:>=2`, `priority:<3` or `priority:<=3`.
+fn parse_priority(value: &str) -> Result<Filter> {
+    let (comparison, number) = [
+        (">=", Comparison::GreaterOrEqual),
+        ("<=", Comparison::LessOrEqual),
+        (">", Comparison::Greater),
+        ("<", Comparison::Less),
+        ("=", Comparison::Equal),
+    ]
+    .into_iter()
+    .find_map(|(prefix, comparison)| value.strip_prefix(prefix).map(|rest| (comparison, rest)))
+    .unwrap_or((Comparison::Equal, value));
+
+    match number.parse::<u8>() {
+        Ok(bound) => Ok(Filter::Priority(comparison, bound)),
+        Err(_) => bail!("Invalid priority '{}'; use a number such as 2, >2 or <=3", value),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Journal;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn filters(input: &str) -> Vec<Filter> {
+        match Query::parse(input).unwrap().filter {
+            Filter::All(filters) => filters,
+            filter => panic!("{:?} is not a list of filters", filter),
+        }
+    }
+
+    fn error(input: &str) -> String {
+        Query::parse(input).unwrap_err().to_string()
+    }
+
+    fn matches(input: &str, entry: &Entry) -> bool {
+        Query::parse(input).unwrap().rank(entry, entry.date).is_some()
+    }
+
+    #[test]
+    fn tokens_split_on_whitespace_outside_quotes() {
+        let tokens = tokenize(r#"  type:task "release notes"  -#home -"big win" tag:"" "#).unwrap();
+        let pieces: Vec<_> = tokens
+            .iter()
+            .map(|token| (token.negated, token.key.as_deref(), token.value.as_str(), token.quoted))
+            .collect();
+        assert_eq!(
+            pieces,
+            [
+                (false, Some("type"), "task", false),
+                (false, None, "release notes", true),
+                (true, None, "#home", false),
+                (true, None, "big win", true),
+                (false, Some("tag"), "", true),
+            ]
+        );
+        assert!(tokenize("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn terms_become_filters() {
+        assert_eq!(
+            filters(r#"type:Task status:complete tag:#Work budget "Q4 plan" -draft 10:30"#),
+            [
+                Filter::Type(BulletType::Task),
+                Filter::Status(TaskStatus::Complete),
+                Filter::Tag("work".to_string()),
+                Filter::Text(Term::new("budget")),
+                Filter::Phrase(Term::new("q4 plan")),
+                Filter::Not(Box::new(Filter::Phrase(Term::new("draft")))),
+                Filter::Phrase(Term::new("10:30")),
+            ]
+        );
+        assert_eq!(filters("-tag:home"), [Filter::Not(Box::new(Filter::Tag("home".to_string())))]);
+        assert_eq!(filters(""), []);
+    }
+
+    #[test]
+    fn negated_terms_exclude_entries() {
+        let entry = Entry::new("Plan the garden #home".to_string(), BulletType::Task, date(2026, 10, 1));
+        assert!(matches("garden", &entry));
+        assert!(!matches("garden -#home", &entry));
+        assert!(!matches("-tag:home", &entry));
+        assert!(matches("-type:note -gardening", &entry));
+    }
+
+    #[test]
+    fn malformed_queries_are_rejected() {
+        assert_eq!(error(r#"say "hello"#), "Missing closing quote after \"hello");
+        assert_eq!(error("type:"), "'type:' needs a value");
+        assert_eq!(error("budget -"), "'-' needs a term to exclude");
+        assert_eq!(error("kind:task"), "Unknown filter 'kind:'; use type, status, tag, date or priority");
+        assert_eq!(error("type:idea"), "Unknown type 'idea'; use task, event or note");
+        assert!(error("status:done").starts_with("Unknown status 'done'"));
+        assert!(error("priority:high").starts_with("Invalid priority 'high'"));
+        assert!(error("date:yesterday").starts_with("Invalid date 'yesterday'"));
+        assert_eq!(error("date:.."), "Date range '..' needs at least one end");
+        assert_eq!(error("date:2026-10..2026-09"), "Date range '2026-10..2026-09' ends before it starts");
+    }
+
+    #[test]
+    fn dates_cover_days_months_years_and_open_ranges() {
+        let range = |input: &str| filters(input).remove(0);
+        assert_eq!(range("date:2026-09-14"), Filter::Date(Some(date(2026, 9, 14)), Some(date(2026, 9, 14))));
+        assert_eq!(range("date:2026-02"), Filter::Date(Some(date(2026, 2, 1)), Some(date(2026, 2, 28))));
+        assert_eq!(range("date:2026"), Filter::Date(Some(date(2026, 1, 1)), Some(date(2026, 12, 31))));
+        assert_eq!(range("date:2026-09.."), Filter::Date(Some(date(2026, 9, 1)), None));
+        assert_eq!(range("date:..2026-09"), Filter::Date(None, Some(date(2026, 9, 30))));
+        assert_eq!(
+            range("date:2026-09-14..2026-10"),
+            Filter::Date(Some(date(2026, 9, 14)), Some(date(2026, 10, 31)))
+        );
+
+        let today = Local::now().date_naive();
+        let three_days_ago = today - TimeDelta::days(3);
+        assert_eq!(range("date:today-3..today"), Filter::Date(Some(three_days_ago), Some(today)));
+    }
+
+    #[test]
+    fn whole_month_entries_match_any_day_of_their_month() {
+        let mut entry = Entry::new("Renew passport".to_string(), BulletType::Task, date(2031, 12, 1));
+        entry.set_date(date(2031, 12, 1), true);
+        assert!(matches("date:2031-12-15", &entry));
+        assert!(matches("date:2031-12-31..", &entry));
+        assert!(matches("date:..2031-12-01", &entry));
+        assert!(!matches("date:..2031-11", &entry));
+        assert!(!matches("date:2032-01-01..", &entry));
+
+        let mut journal = Journal::default();
+        journal.add_entry(entry);
+        assert_eq!(journal.search_entries(&Query::parse("date:2031-12-15").unwrap()).len(), 1);
+    }
+
+    #[test]
+    fn priorities_compare_with_unprioritised_entries_as_zero() {
+        let mut entry = Entry::new("Pay rent".to_string(), BulletType::Task, date(2026, 10, 1));
+        assert!(matches("priority:0", &entry));
+        assert!(matches("priority:<1", &entry));
+        assert!(!matches("priority:>=1", &entry));
+
+        entry.priority = Some(3);
+        for (input, expected) in [
+            ("priority:3", true),
+            ("priority:=3", true),
+            ("priority:>2", true),
+            ("priority:>3", false),
+            ("priority:>=3", true),
+            ("priority:<3", false),
+            ("priority:<=3", true),
+        ] {
+            assert_eq!(matches(input, &entry), expected, "{}", input);
+        }
+    }
+}
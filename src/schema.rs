@@ -0,0 +1,186 @@
+use crate::models::{Entry, Journal};
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// Version of the journal document this build reads and writes. Bump it and
+/// add a step to `MIGRATIONS` whenever the serialized shape of the journal changes.
+pub const SCHEMA_VERSION: u64 = 5;
+
+/// Upgrade steps; `MIGRATIONS[n]` turns a version `n` document into version `n + 1`.
+/// Steps must leave already-upgraded parts alone, since backends that store
+/// entries separately may hold entries newer than their journal document.
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[
+    v0_add_status_history,
+    v1_extract_inline_tags,
+    v2_add_inspiration,
+    v3_add_saved_searches,
+    v4_add_whole_month,
+];
+
+/// Version 0 journals predate status history and the version header.
+fn v0_add_status_history(document: &mut Map<String, Value>) {
+    for_each_entry(document, |entry| {
+        entry.entry("status_history").or_insert_with(|| json!([]));
+    });
+}
+
+/// Version 1 journals never filled `tags` from `#tags` and `@contexts` in the content.
+fn v1_extract_inline_tags(document: &mut Map<String, Value>) {
+    for_each_entry(document, |entry| {
+        let Some(content) = entry.get("content").and_then(Value::as_str) else {
+            return;
+        };
+        let found = v1_inline_tags(content);
+        let Value::Array(tags) = entry.entry("tags").or_insert_with(|| json!([])) else {
+            return;
+        };
+        for tag in found {
+            if !tags.iter().any(|existing| existing.as_str() == Some(tag.as_str())) {
+                tags.push(Value::String(tag));
+            }
+        }
+    });
+}
+
+/// `#tags` and `@contexts` as read when version 2 introduced them, kept apart
+/// from `models::inline_tags` so that changes to the tag syntax leave upgrades
+/// of version 1 journals as they were.
+fn v1_inline_tags(content: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let mut previous = None;
+    let mut chars = content.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let starts_word = previous.is_none_or(|p: char| p.is_whitespace() || p == '(');
+        previous = Some(c);
+        if !(starts_word && (c == '#' || c == '@')) || !chars.peek().is_some_and(|(_, next)| next.is_alphabetic()) {
+            continue;
+        }
+        let mut end = start + 1;
+        while let Some(&(index, next)) = chars.peek() {
+            if !(next.is_alphanumeric() || matches!(next, '-' | '_' | '/')) {
+                break;
+            }
+            end = index + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+        let token = content[start..end].trim_end_matches(['-', '_', '/']);
+        let tag = token.strip_prefix('#').unwrap_or(token).to_lowercase();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Version 2 journals predate the `!` inspiration signifier.
+fn v2_add_inspiration(document: &mut Map<String, Value>) {
+    for_each_entry(document, |entry| {
+        entry.entry("inspiration").or_insert(Value::Bool(false));
+    });
+}
+
+/// Version 3 journals predate saved searches.
+fn v3_add_saved_searches(document: &mut Map<String, Value>) {
+    document.entry("saved_searches").or_insert_with(|| json!({}));
+}
+
+/// Version 4 journals predate entries planned for a whole month.
+fn v4_add_whole_month(document: &mut Map<String, Value>) {
+    for_each_entry(document, |entry| {
+        entry.entry("whole_month").or_insert(Value::Bool(false));
+    });
+}
+
+fn for_each_entry(document: &mut Map<String, Value>, mut f: impl FnMut(&mut Map<String, Value>)) {
+    if let Some(Value::Array(entries)) = document.get_mut("entries") {
+        entries.iter_mut().filter_map(Value::as_object_mut).for_each(&mut f);
+    }
+    if let Some(Value::Object(collections)) = document.get_mut("collections") {
+        for collection in collections.values_mut() {
+            if let Some(Value::Array(entries)) = collection.get_mut("entries") {
+                entries.iter_mut().filter_map(Value::as_object_mut).for_each(&mut f);
+            }
+        }
+    }
+}
+
+/// The schema version a document was written with; documents without one are version 0.
+pub fn document_version(document: &Value) -> u64 {
+    document.get("schema_version").and_then(Value::as_u64).unwrap_or(0)
+}
+
+/// Brings a journal document up to `SCHEMA_VERSION`, one step at a time.
+/// Refuses documents from newer builds rather than silently dropping their fields.
+pub fn upgrade(document: &mut Value) -> Result<()> {
+    let version = document_version(document);
+    if version > SCHEMA_VERSION {
+        bail!(
+            "Journal uses schema version {}, but this build of bujo only understands up to version {}; please upgrade bujo",
+            version,
+            SCHEMA_VERSION
+        );
+    }
+
+    let Value::Object(fields) = document else {
+        bail!("Journal file is not a JSON object");
+    };
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(fields);
+    }
+    fields.insert("schema_version".to_string(), json!(SCHEMA_VERSION));
+    Ok(())
+}
+
+/// Parses a journal document of any supported version.
+pub fn journal_from_value(mut document: Value) -> Result<Journal> {
+    upgrade(&mut document)?;
+    let mut journal: Journal = serde_json::from_value(document).context("Could not parse journal file")?;
+    // Files edited by hand may list entries out of date order.
+    if !journal.entries().is_sorted_by_key(|entry| entry.date) {
+        journal.entries_mut().sort_by_key(|entry| entry.date);
+    }
+    Ok(journal)
+}
+
+pub fn journal_from_str(content: &str) -> Result<Journal> {
+    let document: Value = serde_json::from_str(content)
+        .context("Could not parse journal file")?;
+    journal_from_value(document)
+}
+
+/// Upgrades entries stored apart from their journal document, written at `version`.
+pub fn entries_from_values(version: u64, entries: Vec<Value>) -> Result<Vec<Entry>> {
+    let mut document = json!({ "schema_version": version, "entries": entries });
+    upgrade(&mut document)?;
+    serde_json::from_value(document["entries"].take()).context("Could not parse journal entries")
+}
+
+/// A journal as written to disk, led by its schema version.
+#[derive(Serialize)]
+struct Document<'a> {
+    schema_version: u64,
+    #[serde(flatten)]
+    journal: &'a Journal,
+}
+
+pub fn journal_to_string(journal: &Journal) -> Result<String> {
+    let document = Document { schema_version: SCHEMA_VERSION, journal };
+    serde_json::to_string_pretty(&document).context("Could not serialize journal")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v1_upgrade_adds_inline_tags_once() {
+        let mut document = json!({
+            "entries": [{ "content": "Call (@phone) about #Q4-plan/ and #q4-plan, not a#b or #1", "tags": ["work"] }],
+        });
+        let Value::Object(document) = &mut document else { unreachable!() };
+        v1_extract_inline_tags(document);
+        assert_eq!(document["entries"][0]["tags"], json!(["work", "@phone", "q4-plan"]));
+    }
+}
@@ -0,0 +1,175 @@
+use super::{write_atomic, Backend};
+use crate::models::Journal;
+use crate::schema;
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lets scripts and cron jobs open an encrypted journal without a terminal.
+pub const PASSPHRASE_ENV: &str = "BUJO_PASSPHRASE";
+
+// File layout: MAGIC, SALT_LEN bytes of salt, NONCE_LEN bytes of nonce, then
+// the sealed JSON document. The magic and salt are authenticated along with it.
+const MAGIC: &[u8] = b"BUJOENC1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const HEADER_LEN: usize = MAGIC.len() + SALT_LEN + NONCE_LEN;
+const PROMPT_ATTEMPTS: usize = 3;
+
+/// A key derived from the passphrase, bound to the salt it was derived with.
+#[derive(Clone)]
+pub struct Cipher {
+    salt: [u8; SALT_LEN],
+    aead: XChaCha20Poly1305,
+}
+
+impl Cipher {
+    fn derive(passphrase: &str, salt: [u8; SALT_LEN]) -> Result<Self> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| anyhow!("Could not derive key from passphrase: {}", e))?;
+        let aead = XChaCha20Poly1305::new(Key::from_slice(&key));
+        key.fill(0);
+        Ok(Self { salt, aead })
+    }
+
+    fn associated_data(&self) -> Vec<u8> {
+        [MAGIC, &self.salt].concat()
+    }
+
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = self.associated_data();
+        let ciphertext = self.aead
+            .encrypt(&nonce, Payload { msg: plaintext, aad: &aad })
+            .map_err(|_| anyhow!("Could not encrypt journal"))?;
+        Ok([MAGIC, &self.salt, nonce.as_slice(), &ciphertext].concat())
+    }
+
+    /// Decrypts a sealed file, or returns `None` if this key did not seal it.
+    fn open(&self, sealed: &[u8]) -> Option<Vec<u8>> {
+        let nonce = XNonce::from_slice(&sealed[MAGIC.len() + SALT_LEN..HEADER_LEN]);
+        let aad = self.associated_data();
+        self.aead
+            .decrypt(nonce, Payload { msg: &sealed[HEADER_LEN..], aad: &aad })
+            .ok()
+    }
+}
+
+/// Reads a sealed file, checking it is one before any key is derived.
+fn read_sealed(path: &Path) -> Result<Vec<u8>> {
+    let sealed = fs::read(path)
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    if sealed.len() < HEADER_LEN || !sealed.starts_with(MAGIC) {
+        bail!("{} is not an encrypted bujo journal", path.display());
+    }
+    Ok(sealed)
+}
+
+fn salt_of(sealed: &[u8]) -> [u8; SALT_LEN] {
+    let mut salt = [0u8; SALT_LEN];
+    salt.copy_from_slice(&sealed[MAGIC.len()..MAGIC.len() + SALT_LEN]);
+    salt
+}
+
+/// Derives the key for the encrypted journal at `path`, asking for the
+/// passphrase unless `BUJO_PASSPHRASE` is set. A journal that does not exist
+/// yet gets a fresh salt and a confirmed new passphrase.
+pub fn unlock(path: &Path, journal: &str) -> Result<Cipher> {
+    let from_env = std::env::var(PASSPHRASE_ENV).ok();
+
+    if !path.exists() {
+        let passphrase = match from_env {
+            Some(passphrase) => passphrase,
+            None => {
+                let passphrase = prompt(&format!("New passphrase for journal {}: ", journal))?;
+                if prompt("Repeat passphrase: ")? != passphrase {
+                    bail!("Passphrases do not match");
+                }
+                passphrase
+            }
+        };
+        if passphrase.is_empty() {
+            bail!("The passphrase must not be empty");
+        }
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        return Cipher::derive(&passphrase, salt);
+    }
+
+    let sealed = read_sealed(path)?;
+    let salt = salt_of(&sealed);
+    if let Some(passphrase) = from_env {
+        let cipher = Cipher::derive(&passphrase, salt)?;
+        if cipher.open(&sealed).is_none() {
+            bail!("Wrong passphrase for journal {} (from {})", journal, PASSPHRASE_ENV);
+        }
+        return Ok(cipher);
+    }
+
+    for attempt in 1..=PROMPT_ATTEMPTS {
+        let cipher = Cipher::derive(&prompt(&format!("Passphrase for journal {}: ", journal))?, salt)?;
+        if cipher.open(&sealed).is_some() {
+            return Ok(cipher);
+        }
+        if attempt < PROMPT_ATTEMPTS {
+            eprintln!("Wrong passphrase, try again.");
+        }
+    }
+    bail!("Wrong passphrase for journal {}", journal)
+}
+
+fn prompt(message: &str) -> Result<String> {
+    rpassword::prompt_password(message).context("Could not read passphrase from the terminal")
+}
+
+/// Decrypts a sealed file written with the same key, such as a backup.
+pub fn read_sealed_journal(path: &Path, cipher: &Cipher) -> Result<Journal> {
+    let sealed = read_sealed(path)?;
+    if salt_of(&sealed) != cipher.salt {
+        bail!("{} was encrypted with a different passphrase", path.display());
+    }
+    let plaintext = cipher.open(&sealed)
+        .with_context(|| format!("{} could not be decrypted; it may be damaged", path.display()))?;
+    schema::journal_from_str(std::str::from_utf8(&plaintext).context("Could not parse journal file")?)
+}
+
+/// The whole journal as one JSON document, encrypted with a passphrase-derived key.
+pub struct EncryptedBackend {
+    path: PathBuf,
+    cipher: Cipher,
+}
+
+impl EncryptedBackend {
+    pub fn new(path: PathBuf, cipher: Cipher) -> Self {
+        Self { path, cipher }
+    }
+}
+
+impl Backend for EncryptedBackend {
+    fn load(&self) -> Result<Journal> {
+        if !self.path.exists() {
+            return Ok(Journal::default());
+        }
+
+        read_sealed_journal(&self.path, &self.cipher)
+    }
+
+    fn save(&self, journal: &Journal) -> Result<()> {
+        let content = schema::journal_to_string(journal)?;
+
+        write_atomic(&self.path, &self.cipher.seal(content.as_bytes())?)
+            .context("Could not write journal file")?;
+
+        Ok(())
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        Ok(if self.path.exists() { vec![self.path.clone()] } else { Vec::new() })
+    }
+}
@@ -0,0 +1,47 @@
+use super::{write_atomic, Backend};
+use crate::models::Journal;
+use crate::schema;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The whole journal as one pretty-printed JSON file.
+pub struct JsonBackend {
+    path: PathBuf,
+}
+
+impl JsonBackend {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Backend for JsonBackend {
+    fn load(&self) -> Result<Journal> {
+        if !self.path.exists() {
+            return Ok(Journal::default());
+        }
+
+        read_journal(&self.path)
+    }
+
+    fn save(&self, journal: &Journal) -> Result<()> {
+        let content = schema::journal_to_string(journal)?;
+
+        write_atomic(&self.path, content.as_bytes())
+            .context("Could not write journal file")?;
+
+        Ok(())
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        Ok(if self.path.exists() { vec![self.path.clone()] } else { Vec::new() })
+    }
+}
+
+pub fn read_journal(path: &Path) -> Result<Journal> {
+    let content = fs::read_to_string(path)
+        .context("Could not read journal file")?;
+
+    schema::journal_from_str(&content)
+}
@@ -0,0 +1,497 @@
+use super::{write_atomic, Backend};
+use crate::models::{BulletType, Collection, Entry, Journal, TaskStatus};
+use crate::schema;
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDate};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const JOURNAL_FILE: &str = "bujo.json";
+const COLLECTIONS_DIR: &str = "collections";
+const METADATA_START: &str = "<!-- ";
+const METADATA_END: &str = " -->";
+/// Namespace of the ids derived for lines written by hand.
+const HAND_WRITTEN: Uuid = Uuid::from_u128(0x375773a2_c6e6_44db_9323_90796476ad31);
+
+/// The journal as a tree of Markdown files meant to be read and versioned
+/// without bujo: `YYYY/MM/YYYY-MM-DD.md` per day, one file per collection under
+/// `collections/`, and the saved searches and settings in `bujo.json`.
+///
+/// Each entry is one line starting with its bullet symbol. Whatever the line
+/// itself cannot show (id, creation time, tags, priority, status history) is
+/// kept as JSON in an HTML comment at the end of the line, which Markdown
+/// renderers hide. Lines written by hand without that comment are read as new
+/// entries, with ids derived from the line so they keep them until saved.
+pub struct MarkdownBackend {
+    root: PathBuf,
+}
+
+impl MarkdownBackend {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn day_path(&self, date: NaiveDate) -> PathBuf {
+        self.root
+            .join(date.format("%Y").to_string())
+            .join(date.format("%m").to_string())
+            .join(format!("{}.md", date.format("%Y-%m-%d")))
+    }
+
+    fn collection_path(&self, collection: &Collection) -> PathBuf {
+        let id = collection.id.simple().to_string();
+        self.root
+            .join(COLLECTIONS_DIR)
+            .join(format!("{}-{}.md", slug(&collection.name), &id[..8]))
+    }
+
+    /// Day files with their dates, oldest first.
+    fn day_files(&self) -> Result<Vec<(NaiveDate, PathBuf)>> {
+        let mut files = Vec::new();
+        for year in subdirectories(&self.root, 4)? {
+            for month in subdirectories(&year, 2)? {
+                for path in markdown_files(&month)? {
+                    let date = path
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .and_then(|stem| NaiveDate::parse_from_str(stem, "%Y-%m-%d").ok());
+                    if let Some(date) = date {
+                        files.push((date, path));
+                    }
+                }
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    fn collection_files(&self) -> Result<Vec<PathBuf>> {
+        markdown_files(&self.root.join(COLLECTIONS_DIR))
+    }
+
+    /// The contents of `bujo.json`: the journal document without its entries.
+    fn journal_document(&self) -> Result<Value> {
+        let journal_path = self.root.join(JOURNAL_FILE);
+        let content = if journal_path.exists() {
+            fs::read_to_string(&journal_path).context("Could not read journal file")?
+        } else {
+            schema::journal_to_string(&Journal::default())?
+        };
+        serde_json::from_str(&content).context("Could not parse journal file")
+    }
+
+    fn read_day(&self, date: NaiveDate, path: &Path) -> Result<Vec<Value>> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        let mut ids = LineIds::new(date.to_string());
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !is_heading(line))
+            .map(|line| parse_entry(line, Some(date), &mut ids))
+            .collect::<Result<_>>()
+            .with_context(|| format!("Could not parse {}", path.display()))
+    }
+}
+
+impl Backend for MarkdownBackend {
+    fn load(&self) -> Result<Journal> {
+        let mut entries = Vec::new();
+        for (date, path) in self.day_files()? {
+            entries.extend(self.read_day(date, &path)?);
+        }
+
+        let mut collections = Map::new();
+        for path in self.collection_files()? {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Could not read {}", path.display()))?;
+            let collection = parse_collection(&content)
+                .with_context(|| format!("Could not parse {}", path.display()))?;
+            let id = collection["id"].as_str().unwrap_or_default().to_string();
+            collections.insert(id, collection);
+        }
+
+        let mut document = self.journal_document()?;
+        document["entries"] = Value::Array(entries);
+        document["collections"] = Value::Object(collections);
+        schema::journal_from_value(document)
+    }
+
+    /// Writes only the files whose content changed, so the tree stays friendly
+    /// to version control, and removes day and collection files no longer needed.
+    fn save(&self, journal: &Journal) -> Result<()> {
+        let mut files: BTreeMap<PathBuf, String> = BTreeMap::new();
+
+        let mut skeleton = journal.clone();
+        skeleton.entries_mut().clear();
+        skeleton.collections.clear();
+        files.insert(self.root.join(JOURNAL_FILE), schema::journal_to_string(&skeleton)?);
+
+        let mut days: BTreeMap<NaiveDate, Vec<&Entry>> = BTreeMap::new();
+        for entry in journal.entries() {
+            days.entry(entry.date).or_default().push(entry);
+        }
+        for (date, entries) in days {
+            let mut content = format!("# {}\n\n", date.format("%A, %B %d, %Y"));
+            for entry in entries {
+                content.push_str(&format_entry(entry, false)?);
+                content.push('\n');
+            }
+            files.insert(self.day_path(date), content);
+        }
+
+        for collection in journal.collections.values() {
+            files.insert(self.collection_path(collection), format_collection(collection)?);
+        }
+
+        let existing: Vec<PathBuf> = self.day_files()?
+            .into_iter()
+            .map(|(_, path)| path)
+            .chain(self.collection_files()?)
+            .collect();
+        for path in existing {
+            if !files.contains_key(&path) {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Could not remove {}", path.display()))?;
+            }
+        }
+
+        for (path, content) in files {
+            if fs::read_to_string(&path).is_ok_and(|current| current == content) {
+                continue;
+            }
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)
+                    .with_context(|| format!("Could not create {}", dir.display()))?;
+            }
+            write_atomic(&path, content.as_bytes())
+                .with_context(|| format!("Could not write {}", path.display()))?;
+        }
+
+        Ok(())
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        let journal_path = self.root.join(JOURNAL_FILE);
+        let mut files: Vec<PathBuf> = self.day_files()?
+            .into_iter()
+            .map(|(_, path)| path)
+            .chain(self.collection_files()?)
+            .collect();
+        if journal_path.exists() {
+            files.push(journal_path);
+        }
+        Ok(files)
+    }
+
+    fn entries_in_range(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Vec<Entry>> {
+        let mut entries = Vec::new();
+        for (date, path) in self.day_files()? {
+            if from.is_none_or(|from| date >= from) && to.is_none_or(|to| date <= to) {
+                entries.extend(self.read_day(date, &path)?);
+            }
+        }
+        let version = schema::document_version(&self.journal_document()?);
+        schema::entries_from_values(version, entries)
+    }
+}
+
+/// Directories directly inside `dir` whose names are `digits` ASCII digits.
+fn subdirectories(dir: &Path, digits: usize) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Could not read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.len() == digits && name.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn markdown_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Could not read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() { "collection".to_string() } else { slug.to_string() }
+}
+
+fn parse_symbol(symbol: &str) -> Option<(BulletType, Option<TaskStatus>)> {
+    Some(match symbol {
+        "•" => (BulletType::Task, Some(TaskStatus::Incomplete)),
+        "✓" => (BulletType::Task, Some(TaskStatus::Complete)),
+        ">" => (BulletType::Task, Some(TaskStatus::Migrated)),
+        "<" => (BulletType::Task, Some(TaskStatus::Scheduled)),
+        "✗" => (BulletType::Task, Some(TaskStatus::Irrelevant)),
+        "○" => (BulletType::Event, None),
+        "-" => (BulletType::Note, None),
+        _ => return None,
+    })
+}
+
+/// One entry as a line: its symbol, content and a metadata comment holding the
+/// remaining fields. Entries in day files leave their date to the file name.
+fn format_entry(entry: &Entry, with_date: bool) -> Result<String> {
+    let Value::Object(mut metadata) = serde_json::to_value(entry)? else {
+        unreachable!("entries serialize to objects");
+    };
+
+    // Fields the line already shows, unless the line cannot show them faithfully.
+    let single_line = !entry.content.contains(['\n', '\r']);
+    if single_line {
+        metadata.remove("content");
+    }
+    if !with_date {
+        metadata.remove("date");
+    }
+    metadata.remove("bullet_type");
+    metadata.remove("status");
+    // Left out, `tags` would be read back from the content's inline tags,
+    // undoing any that were removed; other fields default to their empty value.
+    metadata.retain(|key, value| key == "tags" || !is_empty(value));
+    // A task without a status shares its symbol with an incomplete one.
+    if entry.bullet_type == BulletType::Task && entry.status.is_none() {
+        metadata.insert("status".to_string(), Value::Null);
+    }
+
+    let text = if single_line { entry.content.clone() } else { entry.content.replace(['\n', '\r'], " ") };
+    Ok(format!("{} {}{}", entry.symbol(), text, format_metadata(&metadata)?))
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+fn format_metadata(metadata: &Map<String, Value>) -> Result<String> {
+    // Escaping angle brackets keeps the JSON from closing the comment early.
+    let json = serde_json::to_string(metadata)?
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e");
+    Ok(format!(" {}{}{}", METADATA_START, json, METADATA_END))
+}
+
+/// Whether the line is a Markdown heading, such as a day file's date, rather
+/// than an entry that starts with a `#tag`.
+fn is_heading(line: &str) -> bool {
+    let level = line.len() - line.trim_start_matches('#').len();
+    (1..=6).contains(&level) && (line.len() == level || line[level..].starts_with(' '))
+}
+
+/// Ids for entry lines without metadata to keep one in. A line keeps its id
+/// from one load to the next; repeats of a line in the same file are told
+/// apart by how many came before them.
+struct LineIds<'a> {
+    file: String,
+    seen: HashMap<&'a str, usize>,
+}
+
+impl<'a> LineIds<'a> {
+    fn new(file: String) -> Self {
+        Self { file, seen: HashMap::new() }
+    }
+
+    fn next(&mut self, line: &'a str) -> Uuid {
+        let repeat = self.seen.entry(line).or_default();
+        let name = format!("{}\n{}\n{}", self.file, repeat, line);
+        *repeat += 1;
+        Uuid::new_v5(&HAND_WRITTEN, name.as_bytes())
+    }
+}
+
+/// Splits a trailing metadata comment off a line.
+fn split_metadata(line: &str) -> (&str, Map<String, Value>) {
+    if let Some(stripped) = line.strip_suffix(METADATA_END) {
+        if let Some(start) = stripped.rfind(METADATA_START) {
+            let json = &stripped[start + METADATA_START.len()..];
+            if let Ok(Value::Object(metadata)) = serde_json::from_str(json) {
+                let text = &line[..start];
+                return (text.strip_suffix(' ').unwrap_or(text), metadata);
+            }
+        }
+    }
+    (line, Map::new())
+}
+
+/// Reads an entry line into its serialized fields, left for the schema upgrade
+/// to deserialize. Lines without a recognised symbol become notes.
+fn parse_entry<'a>(line: &'a str, date: Option<NaiveDate>, ids: &mut LineIds<'a>) -> Result<Value> {
+    let (text, metadata) = split_metadata(line);
+    let (symbol, content) = text.split_once(' ').unwrap_or((text, ""));
+    let (bullet_type, status, content) = match parse_symbol(symbol) {
+        Some((bullet_type, status)) => (bullet_type, status, content),
+        None => (BulletType::Note, None, text),
+    };
+
+    let date = date.unwrap_or_else(|| Local::now().date_naive());
+    let mut fields = match serde_json::to_value(Entry::new(content.to_string(), bullet_type, date))? {
+        Value::Object(fields) => fields,
+        _ => unreachable!("entries serialize to objects"),
+    };
+    fields.insert("status".to_string(), serde_json::to_value(status)?);
+    if !metadata.contains_key("id") {
+        fields.insert("id".to_string(), Value::String(ids.next(line).to_string()));
+    }
+    fields.extend(metadata);
+
+    Ok(Value::Object(fields))
+}
+
+/// A collection file: the name as a heading, the description as a quote, a
+/// metadata comment line and then the entries, which keep their dates in metadata.
+fn format_collection(collection: &Collection) -> Result<String> {
+    let mut content = format!("# {}\n\n", collection.name);
+    if let Some(description) = &collection.description {
+        for line in description.lines() {
+            content.push_str(&format!("> {}\n", line));
+        }
+        content.push('\n');
+    }
+
+    let Value::Object(mut metadata) = serde_json::to_value(collection)? else {
+        unreachable!("collections serialize to objects");
+    };
+    metadata.remove("name");
+    metadata.remove("description");
+    metadata.remove("entries");
+    content.push_str(format_metadata(&metadata)?.trim_start());
+    content.push_str("\n\n");
+
+    for entry in &collection.entries {
+        content.push_str(&format_entry(entry, true)?);
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+fn parse_collection(content: &str) -> Result<Value> {
+    let mut name = None;
+    let mut description: Vec<&str> = Vec::new();
+    let mut metadata = Map::new();
+    let mut entries = Vec::new();
+    // The header ends at the metadata line; after it, `>` starts a migrated task.
+    let mut in_header = true;
+    let mut ids = LineIds::new(String::new());
+
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix("# ").filter(|_| name.is_none()) {
+            name = Some(heading.trim().to_string());
+            ids = LineIds::new(format!("collection {}", heading.trim()));
+        } else if let Some(quote) = line.strip_prefix('>').filter(|_| in_header) {
+            description.push(quote.strip_prefix(' ').unwrap_or(quote));
+        } else if line.starts_with(METADATA_START.trim_end()) && in_header {
+            metadata = split_metadata(line).1;
+            in_header = false;
+        } else {
+            in_header = false;
+            entries.push(parse_entry(line, None, &mut ids)?);
+        }
+    }
+
+    let mut fields = match serde_json::to_value(Collection::new(
+        name.unwrap_or_else(|| "Untitled".to_string()),
+        (!description.is_empty()).then(|| description.join("\n")),
+    ))? {
+        Value::Object(fields) => fields,
+        _ => unreachable!("collections serialize to objects"),
+    };
+    fields.extend(metadata);
+    fields.insert("entries".to_string(), Value::Array(entries));
+    Ok(Value::Object(fields))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SavedSearch;
+
+    #[test]
+    fn journals_round_trip() {
+        let root = std::env::temp_dir().join(format!("bujo-markdown-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let backend = MarkdownBackend::new(root.clone());
+        let date = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+
+        let mut journal = Journal::default();
+        let mut untagged = Entry::new("Review #work notes".to_string(), BulletType::Task, date);
+        untagged.tags.clear();
+        let mut planned = Entry::new("Renew <passport>\nbring photos".to_string(), BulletType::Task, date);
+        planned.set_date(date, true);
+        planned.priority = Some(2);
+        planned.inspiration = true;
+        planned.set_status(TaskStatus::Complete).unwrap();
+        let mut unset = Entry::new("Old task".to_string(), BulletType::Task, date);
+        unset.status = None;
+        for entry in [untagged, planned, unset, Entry::new("Dentist".to_string(), BulletType::Event, date)] {
+            journal.add_entry(entry);
+        }
+        let mut collection = Collection::new("Reading list".to_string(), Some("Books\nand papers".to_string()));
+        collection.add_entry(Entry::new("Dune #scifi".to_string(), BulletType::Note, date));
+        journal.collections.insert(collection.id, collection);
+        let search = SavedSearch::new("work".to_string(), "tag:work".to_string());
+        journal.saved_searches.insert(search.id, search);
+
+        backend.save(&journal).unwrap();
+        let loaded = backend.load().unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(loaded.entries(), journal.entries());
+        assert_eq!(loaded.collections, journal.collections);
+        assert_eq!(loaded.saved_searches, journal.saved_searches);
+    }
+
+    #[test]
+    fn hand_written_lines_keep_their_ids_between_loads() {
+        let root = std::env::temp_dir().join(format!("bujo-markdown-hand-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let backend = MarkdownBackend::new(root.clone());
+        let date = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        let path = backend.day_path(date);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "# Wednesday, October 14, 2026\n\n## Morning\n#idea a garden shed\n• Water plants\n• Water plants\n#\n").unwrap();
+
+        let loaded = backend.load().unwrap();
+        let contents: Vec<&str> = loaded.entries().iter().map(|entry| entry.content.as_str()).collect();
+        assert_eq!(contents, ["#idea a garden shed", "Water plants", "Water plants"]);
+        assert_eq!(loaded.entries()[0].tags, ["idea"]);
+        let ids: Vec<Uuid> = loaded.entries().iter().map(|entry| entry.id).collect();
+        assert_ne!(ids[1], ids[2]);
+
+        let reloaded: Vec<Uuid> = backend.load().unwrap().entries().iter().map(|entry| entry.id).collect();
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(reloaded, ids);
+    }
+}
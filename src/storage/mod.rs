@@ -0,0 +1,400 @@
+mod encrypted;
+mod json;
+mod markdown;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+use crate::config::{JournalLocation, StorageBackend};
+use crate::models::{Entry, Journal};
+use crate::schema;
+use anyhow::{bail, Context, Result};
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+pub use encrypted::PASSPHRASE_ENV;
+
+const BACKUP_PREFIX: &str = "journal-";
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+const LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+/// File name of an encrypted journal within its directory.
+pub const ENCRYPTED_FILE: &str = "journal.enc";
+
+/// A format the journal can be persisted in.
+pub trait Backend {
+    fn load(&self) -> Result<Journal>;
+
+    fn save(&self, journal: &Journal) -> Result<()>;
+
+    /// The files currently holding the journal, watched for changes made by other processes.
+    fn files(&self) -> Result<Vec<PathBuf>>;
+
+    /// Adds the entry to the daily log, or replaces the entry with the same id wherever it is.
+    fn upsert_entry(&self, entry: &Entry) -> Result<()> {
+        let mut journal = self.load()?;
+        if !journal.replace_entry(entry.clone()) {
+            journal.add_entry(entry.clone());
+        }
+        self.save(&journal)
+    }
+
+    /// Daily log entries dated within the inclusive range, ordered by date.
+    fn entries_in_range(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Vec<Entry>> {
+        let journal = self.load()?;
+        Ok(journal.entries_in_range(from, to).into_iter().cloned().collect())
+    }
+}
+
+/// Identifies one version of the journal files on disk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fingerprint {
+    modified: Option<SystemTime>,
+    len: u64,
+    hash: u64,
+}
+
+impl Fingerprint {
+    fn of(files: &[PathBuf]) -> Result<Option<Self>> {
+        if files.is_empty() {
+            return Ok(None);
+        }
+        let (modified, len) = file_stats(files)?;
+        Ok(Some(Self { modified, len, hash: hash_files(files)? }))
+    }
+}
+
+/// Latest modification time and total size of the files.
+fn file_stats(files: &[PathBuf]) -> Result<(Option<SystemTime>, u64)> {
+    let mut modified = None;
+    let mut len = 0;
+    for path in files {
+        let metadata = fs::metadata(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        modified = modified.max(metadata.modified().ok());
+        len += metadata.len();
+    }
+    Ok((modified, len))
+}
+
+fn hash_files(files: &[PathBuf]) -> Result<u64> {
+    let mut hasher = DefaultHasher::new();
+    for path in files {
+        path.hash(&mut hasher);
+        fs::read(path)
+            .with_context(|| format!("Could not read {}", path.display()))?
+            .hash(&mut hasher);
+    }
+    Ok(hasher.finish())
+}
+
+/// Advisory lock on the journal, released when dropped.
+pub struct JournalLock {
+    _file: File,
+}
+
+/// The journal on disk: a backend plus the locking, change detection and
+/// backups shared by every backend.
+pub struct Storage {
+    data_dir: PathBuf,
+    backend: Box<dyn Backend>,
+    /// Key of an encrypted journal, also used to seal its backups.
+    cipher: Option<encrypted::Cipher>,
+}
+
+impl Storage {
+    pub fn new(location: &JournalLocation) -> Result<Self> {
+        let data_dir = location.dir.clone();
+        fs::create_dir_all(&data_dir)
+            .with_context(|| format!("Could not create journal directory {}", data_dir.display()))?;
+
+        let mut cipher = None;
+        let backend: Box<dyn Backend> = match location.backend {
+            StorageBackend::Json => Box::new(json::JsonBackend::new(data_dir.join("journal.json"))),
+            #[cfg(feature = "sqlite")]
+            StorageBackend::Sqlite => Box::new(sqlite::SqliteBackend::open(&data_dir.join("journal.db"))?),
+            #[cfg(not(feature = "sqlite"))]
+            StorageBackend::Sqlite => bail!("This build of bujo does not include SQLite support"),
+            StorageBackend::Markdown => Box::new(markdown::MarkdownBackend::new(data_dir.join("journal"))),
+            StorageBackend::Encrypted => {
+                let path = data_dir.join(ENCRYPTED_FILE);
+                let key = encrypted::unlock(&path, &location.label())?;
+                cipher = Some(key.clone());
+                Box::new(encrypted::EncryptedBackend::new(path, key))
+            }
+        };
+
+        Ok(Self { data_dir, backend, cipher })
+    }
+
+    /// Whether opening the journal would have to ask for a passphrase.
+    pub fn needs_passphrase(location: &JournalLocation) -> bool {
+        location.backend == StorageBackend::Encrypted && std::env::var_os(PASSPHRASE_ENV).is_none()
+    }
+
+    pub fn load_journal(&self) -> Result<Journal> {
+        self.backend.load()
+    }
+
+    /// Loads the journal along with the fingerprint of the files it was read from.
+    pub fn load_journal_tracked(&self) -> Result<(Journal, Option<Fingerprint>)> {
+        let fingerprint = Fingerprint::of(&self.backend.files()?)?;
+        Ok((self.backend.load()?, fingerprint))
+    }
+
+    pub fn save_journal(&self, journal: &Journal) -> Result<Option<Fingerprint>> {
+        self.backend.save(journal)?;
+        Fingerprint::of(&self.backend.files()?)
+    }
+
+    pub fn upsert_entry(&self, entry: &Entry) -> Result<()> {
+        self.backend.upsert_entry(entry)
+    }
+
+    pub fn entries_in_range(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Vec<Entry>> {
+        self.backend.entries_in_range(from, to)
+    }
+
+    /// Whether the journal on disk differs from the version identified by `known`.
+    /// Only hashes the files when their size or modification time has moved.
+    pub fn has_changed(&self, known: Option<&Fingerprint>) -> Result<bool> {
+        let files = self.backend.files()?;
+        let Some(known) = known else {
+            return Ok(!files.is_empty());
+        };
+        if files.is_empty() {
+            return Ok(true);
+        }
+        if file_stats(&files)? == (known.modified, known.len) {
+            return Ok(false);
+        }
+        Ok(hash_files(&files)? != known.hash)
+    }
+
+    /// Takes the advisory lock that serializes writes between bujo processes,
+    /// waiting briefly if another process holds it.
+    pub fn lock(&self) -> Result<JournalLock> {
+        let lock_path = self.data_dir.join("journal.lock");
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("Could not open {}", lock_path.display()))?;
+
+        let started = Instant::now();
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(JournalLock { _file: file }),
+                Err(TryLockError::WouldBlock) if started.elapsed() < LOCK_TIMEOUT => {
+                    thread::sleep(Duration::from_millis(50));
+                }
+                Err(TryLockError::WouldBlock) => {
+                    bail!("Journal is locked by another bujo process")
+                }
+                Err(TryLockError::Error(e)) => {
+                    return Err(e).context("Could not lock journal");
+                }
+            }
+        }
+    }
+
+    /// Deletes the files holding the journal, after it has been moved elsewhere.
+    pub fn remove_journal_files(&self) -> Result<()> {
+        for path in self.backend.files()? {
+            fs::remove_file(&path)
+                .with_context(|| format!("Could not remove {}", path.display()))?;
+            // Drop directories the backend emptied, such as a markdown month.
+            let mut dir = path.parent();
+            while let Some(parent) = dir.filter(|dir| *dir != self.data_dir) {
+                if fs::remove_dir(parent).is_err() {
+                    break;
+                }
+                dir = parent.parent();
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a JSON snapshot of the journal into the backup directory under a
+    /// timestamped name, keeping only the `retention` most recent backups.
+    /// Encrypted journals get encrypted snapshots. A retention of 0 disables backups.
+    pub fn backup_journal(&self, retention: usize) -> Result<Option<PathBuf>> {
+        if retention == 0 || self.backend.files()?.is_empty() {
+            return Ok(None);
+        }
+
+        let backup_dir = self.backup_dir();
+        fs::create_dir_all(&backup_dir)
+            .context("Could not create backup directory")?;
+
+        let file_name = format!(
+            "{}{}.{}",
+            BACKUP_PREFIX,
+            Local::now().format(BACKUP_TIMESTAMP_FORMAT),
+            if self.cipher.is_some() { "enc" } else { "json" }
+        );
+        let backup_path = backup_dir.join(file_name);
+        let content = schema::journal_to_string(&self.backend.load()?)?;
+        let content = match &self.cipher {
+            Some(cipher) => cipher.seal(content.as_bytes())?,
+            None => content.into_bytes(),
+        };
+        write_atomic(&backup_path, &content)
+            .context("Could not create backup")?;
+
+        for old_backup in self.list_backups()?.into_iter().skip(retention) {
+            fs::remove_file(&old_backup)
+                .with_context(|| format!("Could not remove old backup {}", old_backup.display()))?;
+        }
+
+        Ok(Some(backup_path))
+    }
+
+    /// Backup files, newest first.
+    pub fn list_backups(&self) -> Result<Vec<PathBuf>> {
+        let backup_dir = self.backup_dir();
+        if !backup_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut backups: Vec<PathBuf> = fs::read_dir(&backup_dir)
+            .context("Could not read backup directory")?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| backup_timestamp(path).is_some())
+            .collect();
+        backups.sort();
+        backups.reverse();
+        Ok(backups)
+    }
+
+    pub fn load_backup(&self, path: &Path) -> Result<Journal> {
+        if !is_encrypted_backup(path) {
+            return json::read_journal(path);
+        }
+        match &self.cipher {
+            Some(cipher) => encrypted::read_sealed_journal(path, cipher),
+            None => bail!("{} is encrypted; run `bujo encrypt` to open encrypted backups", path.display()),
+        }
+    }
+
+    fn backup_dir(&self) -> PathBuf {
+        self.data_dir.join("backups")
+    }
+}
+
+/// When a backup was taken, parsed from its file name.
+pub fn backup_timestamp(path: &Path) -> Option<NaiveDateTime> {
+    let stem = path.file_stem()?.to_str()?;
+    let timestamp = stem.strip_prefix(BACKUP_PREFIX)?;
+    NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT).ok()
+}
+
+pub fn is_encrypted_backup(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "enc")
+}
+
+/// Writes `content` to a temporary file next to `path`, flushes it to disk and
+/// renames it over `path`, so a crash mid-write never leaves a truncated file.
+fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let mut file = File::create(&tmp_path)
+        .with_context(|| format!("Could not create {}", tmp_path.display()))?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp_path, path)
+        .with_context(|| format!("Could not replace {}", path.display()))?;
+
+    // Persist the rename itself; not every platform can open a directory for syncing.
+    if let Some(dir) = path.parent() {
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BulletType;
+    use chrono::Timelike;
+
+    fn json_storage(test: &str) -> (Storage, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("bujo-storage-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let location = JournalLocation { name: None, dir: dir.clone(), backend: StorageBackend::Json };
+        let storage = Storage::new(&location).unwrap();
+        let mut journal = Journal::default();
+        journal.add_entry(Entry::new("Water plants".to_string(), BulletType::Task, Local::now().date_naive()));
+        storage.save_journal(&journal).unwrap();
+        (storage, dir)
+    }
+
+    #[test]
+    fn backups_are_named_after_when_they_were_taken() {
+        let (storage, dir) = json_storage("naming");
+        let before = Local::now().naive_local().with_nanosecond(0).unwrap();
+        let backup = storage.backup_journal(3).unwrap().unwrap();
+
+        assert_eq!(backup.parent(), Some(dir.join("backups").as_path()));
+        assert!(backup.extension().is_some_and(|extension| extension == "json"));
+        assert!(!is_encrypted_backup(&backup));
+        let taken = backup_timestamp(&backup).unwrap();
+        assert!(taken >= before && taken <= Local::now().naive_local(), "{}", taken);
+        assert_eq!(storage.load_backup(&backup).unwrap().entries().len(), 1);
+
+        assert_eq!(backup_timestamp(Path::new("journal-latest.json")), None);
+        assert_eq!(backup_timestamp(Path::new("notes-20260101-090000.json")), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn only_the_most_recent_backups_are_kept() {
+        let (storage, dir) = json_storage("retention");
+        let backups = dir.join("backups");
+        fs::create_dir_all(&backups).unwrap();
+        for name in ["journal-20240101-090000.json", "journal-20250101-090000.json", "journal-20250601-090000.json"] {
+            fs::write(backups.join(name), "{}").unwrap();
+        }
+        fs::write(backups.join("notes.txt"), "kept").unwrap();
+
+        let newest = storage.backup_journal(2).unwrap().unwrap();
+        assert_eq!(storage.list_backups().unwrap(), [newest, backups.join("journal-20250601-090000.json")]);
+        assert!(backups.join("notes.txt").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn a_retention_of_zero_takes_no_backup() {
+        let (storage, dir) = json_storage("disabled");
+        assert_eq!(storage.backup_journal(0).unwrap(), None);
+        assert!(!dir.join("backups").exists());
+        assert!(storage.list_backups().unwrap().is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn encrypted_backups_need_the_journal_key() {
+        let (storage, dir) = json_storage("sealed");
+        let backup = dir.join("backups").join("journal-20260101-090000.enc");
+        fs::create_dir_all(backup.parent().unwrap()).unwrap();
+        fs::write(&backup, b"not json").unwrap();
+
+        assert!(is_encrypted_backup(&backup));
+        assert_eq!(storage.list_backups().unwrap(), std::slice::from_ref(&backup));
+        let error = storage.load_backup(&backup).unwrap_err().to_string();
+        assert!(error.contains("is encrypted"), "{}", error);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,235 @@
+use super::Backend;
+use crate::models::{Entry, Journal};
+use crate::schema;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+// Entries are stored whole as JSON in `data`; the other columns copy out the
+// fields worth indexing. Everything else about the journal (collections,
+// settings) is small and lives as one JSON document in `journal`.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS journal (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    data TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS entries (
+    id TEXT PRIMARY KEY,
+    collection_id TEXT,
+    position INTEGER NOT NULL,
+    date TEXT NOT NULL,
+    status TEXT,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS entries_date ON entries (date);
+CREATE INDEX IF NOT EXISTS entries_status ON entries (status);
+CREATE INDEX IF NOT EXISTS entries_collection ON entries (collection_id, position);
+CREATE TABLE IF NOT EXISTS entry_tags (
+    entry_id TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (entry_id, tag)
+);
+CREATE INDEX IF NOT EXISTS entry_tags_tag ON entry_tags (tag);
+";
+
+/// The journal in a SQLite database, with entries in their own indexed table.
+pub struct SqliteBackend {
+    path: PathBuf,
+    conn: Connection,
+}
+
+impl SqliteBackend {
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Could not open {}", path.display()))?;
+        conn.execute_batch(SCHEMA)
+            .context("Could not create journal tables")?;
+        Ok(Self { path: path.to_path_buf(), conn })
+    }
+
+    /// The journal document without its entries, as last saved.
+    fn journal_document(&self) -> Result<Value> {
+        let data: Option<String> = self.conn
+            .query_row("SELECT data FROM journal WHERE id = 1", [], |row| row.get(0))
+            .optional()?;
+        let data = match data {
+            Some(data) => data,
+            None => schema::journal_to_string(&Journal::default())?,
+        };
+        serde_json::from_str(&data).context("Could not parse journal")
+    }
+
+    /// Rows are written at `SCHEMA_VERSION` but upgraded on read from the
+    /// document's version, so a document older than that is brought up to
+    /// date, rows and all, before single rows are written next to it.
+    fn upgrade_document(&self) -> Result<()> {
+        if schema::document_version(&self.journal_document()?) < schema::SCHEMA_VERSION {
+            self.save(&self.load()?)?;
+        }
+        Ok(())
+    }
+}
+
+impl Backend for SqliteBackend {
+    fn load(&self) -> Result<Journal> {
+        let mut document = self.journal_document()?;
+
+        let mut statement = self.conn.prepare("SELECT collection_id, data FROM entries ORDER BY position")?;
+        let rows = statement.query_map([], |row| {
+            Ok((row.get::<_, Option<String>>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for row in rows {
+            let (collection_id, data) = row?;
+            let entry: Value = serde_json::from_str(&data).context("Could not parse entry")?;
+            let collection_entries = collection_id
+                .and_then(|id| document["collections"].get_mut(&id))
+                .and_then(|collection| collection["entries"].as_array_mut());
+            match collection_entries {
+                Some(entries) => entries.push(entry),
+                // Entries of a collection that no longer exists fall back to the log.
+                None => {
+                    if let Some(entries) = document["entries"].as_array_mut() {
+                        entries.push(entry);
+                    }
+                }
+            }
+        }
+
+        let mut journal = schema::journal_from_value(document)?;
+        journal.entries_mut().sort_by_key(|entry| entry.date);
+
+        Ok(journal)
+    }
+
+    fn save(&self, journal: &Journal) -> Result<()> {
+        let mut skeleton = journal.clone();
+        skeleton.entries_mut().clear();
+        for collection in skeleton.collections.values_mut() {
+            collection.entries.clear();
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO journal (id, data) VALUES (1, ?1)",
+            params![schema::journal_to_string(&skeleton)?],
+        )?;
+        tx.execute("DELETE FROM entry_tags", [])?;
+        tx.execute("DELETE FROM entries", [])?;
+        for (position, entry) in journal.entries().iter().enumerate() {
+            insert_entry(&tx, entry, None, position as i64)?;
+        }
+        for collection in journal.collections.values() {
+            let collection_id = collection.id.to_string();
+            for (position, entry) in collection.entries.iter().enumerate() {
+                insert_entry(&tx, entry, Some(&collection_id), position as i64)?;
+            }
+        }
+        tx.commit().context("Could not write journal database")?;
+
+        Ok(())
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        Ok(vec![self.path.clone()])
+    }
+
+    fn upsert_entry(&self, entry: &Entry) -> Result<()> {
+        self.upgrade_document()?;
+        let tx = self.conn.unchecked_transaction()?;
+        let existing: Option<(Option<String>, i64)> = tx
+            .query_row(
+                "SELECT collection_id, position FROM entries WHERE id = ?1",
+                params![entry.id.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let (collection_id, position) = match existing {
+            Some(existing) => existing,
+            None => {
+                let position = tx.query_row(
+                    "SELECT COALESCE(MAX(position) + 1, 0) FROM entries WHERE collection_id IS NULL",
+                    [],
+                    |row| row.get(0),
+                )?;
+                (None, position)
+            }
+        };
+
+        tx.execute("DELETE FROM entry_tags WHERE entry_id = ?1", params![entry.id.to_string()])?;
+        tx.execute("DELETE FROM entries WHERE id = ?1", params![entry.id.to_string()])?;
+        insert_entry(&tx, entry, collection_id.as_deref(), position)?;
+        tx.commit().context("Could not write journal database")?;
+
+        Ok(())
+    }
+
+    fn entries_in_range(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Vec<Entry>> {
+        let mut statement = self.conn.prepare(
+            "SELECT data FROM entries
+             WHERE collection_id IS NULL
+               AND (?1 IS NULL OR date >= ?1)
+               AND (?2 IS NULL OR date <= ?2)
+             ORDER BY date, position",
+        )?;
+        let rows = statement.query_map(
+            params![from.map(|date| date.to_string()), to.map(|date| date.to_string())],
+            |row| row.get::<_, String>(0),
+        )?;
+
+        let mut entries = Vec::new();
+        for data in rows {
+            entries.push(serde_json::from_str(&data?).context("Could not parse entry")?);
+        }
+        let version = schema::document_version(&self.journal_document()?);
+        schema::entries_from_values(version, entries)
+    }
+}
+
+fn insert_entry(conn: &Connection, entry: &Entry, collection_id: Option<&str>, position: i64) -> Result<()> {
+    let id = entry.id.to_string();
+    conn.prepare_cached(
+        "INSERT INTO entries (id, collection_id, position, date, status, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?
+    .execute(params![
+        id,
+        collection_id,
+        position,
+        entry.date.to_string(),
+        entry.status.map(|status| status.label()),
+        serde_json::to_string(entry)?,
+    ])?;
+
+    let mut insert_tag = conn.prepare_cached("INSERT OR IGNORE INTO entry_tags (entry_id, tag) VALUES (?1, ?2)")?;
+    for tag in &entry.tags {
+        insert_tag.execute(params![id, tag])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::BulletType;
+
+    #[test]
+    fn rows_written_into_an_older_document_are_not_upgraded_twice() {
+        let path = std::env::temp_dir().join(format!("bujo-sqlite-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let backend = SqliteBackend::open(&path).unwrap();
+        let mut document: Value = serde_json::from_str(&schema::journal_to_string(&Journal::default()).unwrap()).unwrap();
+        document["schema_version"] = Value::from(1);
+        backend.conn.execute("INSERT INTO journal (id, data) VALUES (1, ?1)", params![document.to_string()]).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+        let mut untagged = Entry::new("Review #work notes".to_string(), BulletType::Task, date);
+        untagged.tags.clear();
+        backend.upsert_entry(&untagged).unwrap();
+
+        assert_eq!(schema::document_version(&backend.journal_document().unwrap()), schema::SCHEMA_VERSION);
+        assert_eq!(backend.entries_in_range(Some(date), Some(date)).unwrap(), [untagged]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
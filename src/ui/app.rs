@@ -1,11 +1,18 @@
-use crate::config::ConfigManager;
-use crate::models::{Journal, Entry, BulletType};
-use crate::storage::Storage;
-use anyhow::Result;
+use crate::config::{ConfigManager, JournalLocation};
+use crate::history::{History, Operation};
+use crate::merge::merge_journals;
+use crate::models::{parse_entry_date, parse_signifiers, Journal, Entry, BulletType, Collection, SavedSearch, TaskStatus, MAX_PRIORITY};
+use crate::query::Query;
+use crate::storage::{Fingerprint, JournalLock, Storage};
+use crate::ui::command::{parse_command, Command, CommandLine};
+use anyhow::{bail, Result};
 use chrono::{Local, NaiveDate, Datelike};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::prelude::*;
-use std::collections::VecDeque;
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppTab {
@@ -14,9 +21,10 @@ pub enum AppTab {
     Future,
     Collections,
     Search,
+    Tags,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum AppMode {
     Normal,
     Insert,
@@ -28,6 +36,60 @@ pub enum InputMode {
     Task,
     Event,
     Note,
+    CollectionName,
+    CollectionRename,
+    CollectionDescription,
+    SavedSearchName,
+    SavedSearchRename,
+    SavedSearchQuery,
+    EditEntry(Uuid),
+    EditDate(Uuid),
+}
+
+/// Which pane of a list-and-detail view (collections, tags) has the keyboard.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaneFocus {
+    List,
+    Entries,
+}
+
+/// A search result kept between frames, with the content bytes to highlight.
+pub struct SearchHit {
+    pub id: Uuid,
+    pub highlights: Vec<Range<usize>>,
+}
+
+/// A search result and the bytes of its content to highlight.
+pub type FoundEntry<'a> = (&'a Entry, &'a [Range<usize>]);
+
+/// State of the start-of-day review of incomplete tasks from previous days.
+pub struct MigrationReview {
+    pub tasks: Vec<Uuid>,
+    pub selected: usize,
+    pub schedule_input: Option<String>,
+    pub collection_picker: Option<usize>,
+}
+
+impl MigrationReview {
+    fn new(tasks: Vec<Uuid>) -> Self {
+        Self {
+            tasks,
+            selected: 0,
+            schedule_input: None,
+            collection_picker: None,
+        }
+    }
+
+    fn selected_task(&self) -> Option<Uuid> {
+        self.tasks.get(self.selected).copied()
+    }
+
+    fn resolve_selected(&mut self) {
+        if self.selected < self.tasks.len() {
+            self.tasks.remove(self.selected);
+        }
+        self.selected = self.selected.min(self.tasks.len().saturating_sub(1));
+    }
 }
 
 pub struct App {
@@ -38,23 +100,66 @@ pub struct App {
     pub mode: AppMode,
     pub current_date: NaiveDate,
     pub selected_month: (i32, u32),
+    /// Month selected in the Future Log, where new entries there are planned.
+    pub future_month: (i32, u32),
+    /// How many months the Future Log grid shows: 6 or 12.
+    pub future_months_shown: u32,
     pub should_quit: bool,
     pub messages: VecDeque<String>,
     pub input_buffer: String,
+    pub input_cursor: usize,
     pub input_mode: Option<InputMode>,
+    pub command_line: CommandLine,
     pub show_help: bool,
     pub search_query: String,
+    /// Results for `search_query`, best first, refreshed as it or the journal changes.
+    pub search_hits: Vec<SearchHit>,
+    /// Results of each saved search by id, or why its query does not parse,
+    /// refreshed like `search_hits` while the Collections tab is open.
+    pub saved_search_hits: HashMap<Uuid, Result<Vec<SearchHit>, String>>,
+    /// The search result a log was opened from and that log's tab, for Backspace to return to.
+    pub search_return: Option<(AppTab, Uuid)>,
     pub selected_entry: Option<usize>,
+    pub selected_collection: Option<usize>,
+    pub collection_focus: PaneFocus,
+    pub selected_tag: Option<usize>,
+    pub tag_focus: PaneFocus,
+    /// Daily, monthly and future views list the highest priority first.
+    pub sort_by_priority: bool,
+    /// Daily, monthly and future views only list entries at least this important.
+    pub min_priority: Option<u8>,
+    pub migration: Option<MigrationReview>,
+    pub history: History,
+    pub dirty: bool,
+    pub last_change: Option<Instant>,
+    pub confirm_quit: bool,
+    /// The journal as last loaded from or saved to disk, used as the merge base.
+    base: Journal,
+    disk_state: Option<Fingerprint>,
+    pub external_change: bool,
+    pub location: JournalLocation,
+    /// Highlighted row of the journal switcher, when it is open.
+    pub journal_picker: Option<usize>,
 }
 
 impl App {
-    pub fn new() -> Result<Self> {
-        let storage = Storage::new()?;
-        let journal = storage.load_journal()?;
+    pub fn new(journal: Option<&str>) -> Result<Self> {
         let config = ConfigManager::new()?;
+        let location = config.get_config().resolve_journal(journal)?;
+        Self::open(config, location)
+    }
+
+    /// Opens the journal at `location` rather than one picked from the config.
+    pub fn open(config: ConfigManager, location: JournalLocation) -> Result<Self> {
+        let (storage, journal, disk_state) = Self::open_journal(&config, &location)?;
         let today = Local::now().date_naive();
         
-        Ok(Self {
+        let mut app = Self {
+            location,
+            journal_picker: None,
+            base: journal.clone(),
+            disk_state,
+            external_change: false,
             journal,
             storage,
             config,
@@ -62,18 +167,223 @@ impl App {
             mode: AppMode::Normal,
             current_date: today,
             selected_month: (today.year(), today.month()),
+            future_month: shift_month((today.year(), today.month()), 1),
+            future_months_shown: 6,
             should_quit: false,
             messages: VecDeque::new(),
             input_buffer: String::new(),
+            input_cursor: 0,
             input_mode: None,
+            command_line: CommandLine::default(),
             show_help: false,
             search_query: String::new(),
+            search_hits: Vec::new(),
+            saved_search_hits: HashMap::new(),
+            search_return: None,
             selected_entry: None,
-        })
+            selected_collection: None,
+            collection_focus: PaneFocus::List,
+            selected_tag: None,
+            tag_focus: PaneFocus::List,
+            sort_by_priority: false,
+            min_priority: None,
+            migration: None,
+            history: History::default(),
+            dirty: false,
+            last_change: None,
+            confirm_quit: false,
+        };
+        app.start_of_day();
+        Ok(app)
+    }
+
+    fn open_journal(
+        config: &ConfigManager,
+        location: &JournalLocation,
+    ) -> Result<(Storage, Journal, Option<Fingerprint>)> {
+        let storage = Storage::new(location)?;
+        let (journal, disk_state) = storage.load_journal_tracked()?;
+        storage.backup_journal(config.get_config().journal.backup_retention)?;
+        Ok((storage, journal, disk_state))
+    }
+
+    /// Saves the current journal and opens another one in its place.
+    pub fn switch_journal(&mut self, selector: &str) -> Result<()> {
+        let location = self.config.get_config().resolve_journal(Some(selector))?;
+        if location.dir == self.location.dir && location.backend == self.location.backend {
+            return Ok(());
+        }
+        // The passphrase prompt needs the terminal, which the TUI is holding.
+        if Storage::needs_passphrase(&location) {
+            bail!("Journal {} is encrypted; open it with `bujo --journal {}`", location.label(), location.label());
+        }
+        if self.dirty {
+            self.save()?;
+        }
+
+        let (storage, journal, disk_state) = Self::open_journal(&self.config, &location)?;
+        self.storage = storage;
+        self.base = journal.clone();
+        self.journal = journal;
+        self.disk_state = disk_state;
+        self.location = location;
+        let had_history = !self.history.is_empty();
+        self.history = History::default();
+        self.migration = None;
+        self.selected_entry = None;
+        self.selected_collection = None;
+        self.collection_focus = PaneFocus::List;
+        self.selected_tag = None;
+        self.tag_focus = PaneFocus::List;
+
+        let cleared = if had_history { "; undo history cleared" } else { "" };
+        self.add_message(format!("Switched to journal {}{}", self.location.label(), cleared));
+        self.start_of_day();
+        Ok(())
+    }
+
+    /// Deals with incomplete tasks left on previous days, either migrating them
+    /// straight to today or opening the migration review.
+    fn start_of_day(&mut self) {
+        let today = Local::now().date_naive();
+        let planned = self.journal.entries_for_month(today.year(), today.month())
+            .iter()
+            .filter(|entry| entry.whole_month)
+            .count();
+        if planned > 0 {
+            self.add_message(format!("{} item(s) planned for {} are in the Monthly Log", planned, today.format("%B")));
+        }
+        let overdue: Vec<Uuid> = self.journal.overdue_tasks(today)
+            .iter()
+            .map(|entry| entry.id)
+            .collect();
+        if overdue.is_empty() {
+            return;
+        }
+
+        let auto_migrate = self.config.get_config().journal.auto_migrate_tasks
+            || self.journal.settings.auto_migrate_tasks;
+        if auto_migrate {
+            let migrated = self.migrate_tasks(overdue, today);
+            self.add_message(format!("Migrated {} task(s) to today", migrated));
+        } else {
+            self.migration = Some(MigrationReview::new(overdue));
+        }
+    }
+
+    pub fn open_migration_review(&mut self) {
+        let today = Local::now().date_naive();
+        let overdue: Vec<Uuid> = self.journal.overdue_tasks(today)
+            .iter()
+            .map(|entry| entry.id)
+            .collect();
+        if overdue.is_empty() {
+            self.add_message("No incomplete tasks from previous days".to_string());
+        } else {
+            self.migration = Some(MigrationReview::new(overdue));
+        }
+    }
+
+    /// Saves the journal, refusing to overwrite changes another process made
+    /// since it was loaded and asking how to resolve them instead.
+    pub fn save(&mut self) -> Result<()> {
+        let lock = self.storage.lock()?;
+        if self.storage.has_changed(self.disk_state.as_ref())? {
+            self.external_change = true;
+            bail!("Journal was changed by another process");
+        }
+        self.write_journal(&lock)
+    }
+
+    fn write_journal(&mut self, _lock: &JournalLock) -> Result<()> {
+        self.disk_state = self.storage.save_journal(&self.journal)?;
+        self.base = self.journal.clone();
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Picks up changes other processes made to the journal file. Without local
+    /// edits the journal is simply reloaded; otherwise the user is asked.
+    pub fn check_external_changes(&mut self) {
+        if self.external_change {
+            return;
+        }
+        match self.storage.has_changed(self.disk_state.as_ref()) {
+            Ok(false) => {}
+            Ok(true) if self.dirty => self.external_change = true,
+            Ok(true) => match self.reload() {
+                Ok(()) => self.add_message("Reloaded journal changed by another process".to_string()),
+                Err(e) => self.add_message(format!("Reload failed: {}", e)),
+            },
+            Err(e) => self.add_message(format!("Could not check journal file: {}", e)),
+        }
+    }
+
+    /// Replaces the journal with what is on disk, dropping local edits. The undo
+    /// history is kept, as after a merge, since its operations find entries by id.
+    fn reload(&mut self) -> Result<()> {
+        let _lock = self.storage.lock()?;
+        let (journal, disk_state) = self.storage.load_journal_tracked()?;
+        self.base = journal.clone();
+        self.journal = journal;
+        self.disk_state = disk_state;
+        self.dirty = false;
+        self.clamp_selection();
+        Ok(())
+    }
+
+    /// Reloads the journal, dropping local edits along with the undo history that made them.
+    fn discard_local_changes(&mut self) -> Result<()> {
+        self.reload()?;
+        self.history = History::default();
+        Ok(())
+    }
+
+    /// Merges local edits into what is on disk by entry id and saves the result.
+    fn merge_external_changes(&mut self) -> Result<usize> {
+        let lock = self.storage.lock()?;
+        let (theirs, _) = self.storage.load_journal_tracked()?;
+        let merge = merge_journals(&self.base, &self.journal, &theirs);
+        self.journal = merge.journal;
+        self.write_journal(&lock)?;
+        self.clamp_selection();
+        Ok(merge.conflicts)
+    }
+
+    fn overwrite_external_changes(&mut self) -> Result<()> {
+        let lock = self.storage.lock()?;
+        self.write_journal(&lock)
     }
 
-    pub fn save(&self) -> Result<()> {
-        self.storage.save_journal(&self.journal)
+    /// Saves once the journal has gone unchanged for the configured autosave delay.
+    pub fn autosave(&mut self) {
+        let delay = self.config.get_config().journal.autosave_secs;
+        if !self.dirty || delay == 0 {
+            return;
+        }
+        let settled = self.last_change
+            .is_none_or(|changed| changed.elapsed() >= Duration::from_secs(delay));
+        if settled {
+            if let Err(e) = self.save() {
+                // Wait another full delay before retrying.
+                self.last_change = Some(Instant::now());
+                self.add_message(format!("Autosave failed: {}", e));
+            }
+        }
+    }
+
+    /// Quits right away when everything is saved, otherwise asks first.
+    pub fn request_quit(&mut self) {
+        if self.dirty {
+            self.confirm_quit = true;
+        } else {
+            self.should_quit = true;
+        }
+    }
+
+    fn mark_dirty(&mut self) {
+        self.dirty = true;
+        self.last_change = Some(Instant::now());
     }
 
     pub fn add_message(&mut self, message: String) {
@@ -84,6 +394,40 @@ impl App {
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        let query = self.search_query.clone();
+        let result = self.dispatch_key(key);
+        if self.current_tab == AppTab::Search {
+            self.refresh_search();
+            if self.search_query != query {
+                self.selected_entry = (!self.search_hits.is_empty()).then_some(0);
+            }
+        }
+        if self.current_tab == AppTab::Collections {
+            self.refresh_saved_searches();
+        }
+        if self.search_return.as_ref().is_some_and(|(tab, _)| *tab != self.current_tab) {
+            self.search_return = None;
+        }
+        result
+    }
+
+    fn dispatch_key(&mut self, key: KeyEvent) -> Result<()> {
+        if self.external_change {
+            return self.handle_external_change_key(key);
+        }
+
+        if self.confirm_quit {
+            return self.handle_quit_confirm_key(key);
+        }
+
+        if self.journal_picker.is_some() {
+            return self.handle_journal_picker_key(key);
+        }
+
+        if self.migration.is_some() {
+            return self.handle_migration_key(key);
+        }
+
         match self.mode {
             AppMode::Normal => self.handle_normal_key(key),
             AppMode::Insert => self.handle_insert_key(key),
@@ -91,14 +435,135 @@ impl App {
         }
     }
 
+    fn handle_quit_confirm_key(&mut self, key: KeyEvent) -> Result<()> {
+        self.confirm_quit = false;
+        match key.code {
+            KeyCode::Char('y') => match self.save() {
+                Ok(()) => self.should_quit = true,
+                Err(e) => self.add_message(format!("Save failed: {}", e)),
+            },
+            KeyCode::Char('n') => {
+                self.should_quit = true;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_journal_picker_key(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(picked) = self.journal_picker else {
+            return Ok(());
+        };
+        let names: Vec<String> = self.config.get_config()
+            .journal_names()
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down if picked + 1 < names.len() => {
+                self.journal_picker = Some(picked + 1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.journal_picker = Some(picked.saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                self.journal_picker = None;
+                if let Some(name) = names.get(picked) {
+                    if let Err(e) = self.switch_journal(name) {
+                        self.add_message(format!("Could not switch journal: {}", e));
+                    }
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.journal_picker = None;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn open_journal_picker(&mut self) {
+        let current = self.location.name.as_deref();
+        let selected = self.config.get_config()
+            .journal_names()
+            .iter()
+            .position(|name| Some(*name) == current)
+            .unwrap_or(0);
+        self.journal_picker = Some(selected);
+    }
+
+    fn handle_external_change_key(&mut self, key: KeyEvent) -> Result<()> {
+        let result = match key.code {
+            KeyCode::Char('m') => self.merge_external_changes().map(|conflicts| match conflicts {
+                0 => "Merged changes from disk".to_string(),
+                n => format!("Merged changes from disk, kept your version of {} conflicting item(s)", n),
+            }),
+            KeyCode::Char('r') => self
+                .discard_local_changes()
+                .map(|_| "Reloaded journal from disk, local changes and undo history discarded".to_string()),
+            KeyCode::Char('o') => self.overwrite_external_changes().map(|_| "Overwrote journal on disk".to_string()),
+            _ => return Ok(()),
+        };
+        self.external_change = false;
+        match result {
+            Ok(message) => self.add_message(message),
+            Err(e) => self.add_message(format!("Error: {}", e)),
+        }
+        Ok(())
+    }
+
     fn handle_normal_key(&mut self, key: KeyEvent) -> Result<()> {
+        if self.current_tab == AppTab::Collections
+            && self.collection_focus == PaneFocus::List
+            && self.handle_collection_list_key(key)
+        {
+            return Ok(());
+        }
+
+        if self.current_tab == AppTab::Tags
+            && self.tag_focus == PaneFocus::List
+            && key.code == KeyCode::Enter
+        {
+            self.handle_right();
+            return Ok(());
+        }
+
+        if self.current_tab == AppTab::Search && key.code == KeyCode::Enter {
+            self.open_selected_result();
+            return Ok(());
+        }
+
+        if self.current_tab == AppTab::Search && key.code == KeyCode::Char('S') {
+            match self.parsed_search() {
+                Some(Ok(_)) => self.start_input(InputMode::SavedSearchName, String::new()),
+                _ => self.add_message("Type a valid query to save first".to_string()),
+            }
+            return Ok(());
+        }
+
+        if self.search_return.is_some() && key.code == KeyCode::Backspace {
+            self.return_to_search();
+            return Ok(());
+        }
+
         match key.code {
             KeyCode::Char('q') => {
-                self.should_quit = true;
+                self.request_quit();
             }
             KeyCode::Char('?') => {
                 self.show_help = !self.show_help;
             }
+            KeyCode::Char(':') => {
+                self.mode = AppMode::Command;
+                self.command_line.clear();
+            }
+            KeyCode::Char('m') => {
+                self.open_migration_review();
+            }
+            KeyCode::Char('J') => {
+                self.open_journal_picker();
+            }
             KeyCode::Tab => {
                 self.next_tab();
             }
@@ -110,20 +575,85 @@ impl App {
             KeyCode::Char('3') => self.current_tab = AppTab::Future,
             KeyCode::Char('4') => self.current_tab = AppTab::Collections,
             KeyCode::Char('5') => self.current_tab = AppTab::Search,
+            KeyCode::Char('6') => self.current_tab = AppTab::Tags,
             KeyCode::Char('t') => {
-                self.mode = AppMode::Insert;
-                self.input_mode = Some(InputMode::Task);
-                self.input_buffer.clear();
+                self.start_input(InputMode::Task, String::new());
             }
             KeyCode::Char('e') => {
-                self.mode = AppMode::Insert;
-                self.input_mode = Some(InputMode::Event);
-                self.input_buffer.clear();
+                self.start_input(InputMode::Event, String::new());
             }
             KeyCode::Char('n') => {
-                self.mode = AppMode::Insert;
-                self.input_mode = Some(InputMode::Note);
-                self.input_buffer.clear();
+                self.start_input(InputMode::Note, String::new());
+            }
+            KeyCode::Char('i') => {
+                if let Some(entry) = self.selected_entry_id().and_then(|id| self.journal.get_entry(id)) {
+                    let (id, content) = (entry.id, entry.content.clone());
+                    self.start_input(InputMode::EditEntry(id), content);
+                }
+            }
+            KeyCode::Char('D') => {
+                if let Some(entry) = self.selected_entry_id().and_then(|id| self.journal.get_entry(id)) {
+                    let (id, date) = (entry.id, entry.date_label());
+                    self.start_input(InputMode::EditDate(id), date);
+                }
+            }
+            KeyCode::Char('u') => {
+                self.undo();
+            }
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.redo();
+            }
+            KeyCode::Char('T') => {
+                self.cycle_selected_bullet_type();
+            }
+            KeyCode::Char('[') => {
+                self.shift_selected_entry_date(-1);
+            }
+            KeyCode::Char(']') => {
+                self.shift_selected_entry_date(1);
+            }
+            KeyCode::Char('x') => {
+                self.set_selected_status(TaskStatus::Complete);
+            }
+            KeyCode::Char('o') => {
+                self.set_selected_status(TaskStatus::Incomplete);
+            }
+            KeyCode::Char('>') => {
+                self.set_selected_status(TaskStatus::Migrated);
+            }
+            KeyCode::Char('<') => {
+                self.set_selected_status(TaskStatus::Scheduled);
+            }
+            KeyCode::Char('~') => {
+                self.set_selected_status(TaskStatus::Irrelevant);
+            }
+            KeyCode::Char('H') => {
+                self.show_selected_status_history();
+            }
+            KeyCode::Char('+') => {
+                self.adjust_selected_priority(true);
+            }
+            KeyCode::Char('-') => {
+                self.adjust_selected_priority(false);
+            }
+            KeyCode::Char('!') => {
+                self.toggle_selected_inspiration();
+            }
+            KeyCode::Char('P') => {
+                self.sort_by_priority = !self.sort_by_priority;
+                self.selected_entry = None;
+                self.add_message(if self.sort_by_priority {
+                    "Sorting by priority".to_string()
+                } else {
+                    "Sorting by date".to_string()
+                });
+            }
+            KeyCode::Char('F') => {
+                self.cycle_priority_filter();
+            }
+            KeyCode::Char('v') if self.current_tab == AppTab::Future => {
+                self.future_months_shown = if self.future_months_shown == 6 { 12 } else { 6 };
+                self.add_message(format!("Showing {} months", self.future_months_shown));
             }
             KeyCode::Char('j') | KeyCode::Down => {
                 self.move_selection_down();
@@ -140,22 +670,18 @@ impl App {
             KeyCode::Enter | KeyCode::Char(' ') => {
                 self.toggle_selected_entry();
             }
-            KeyCode::Char('d') => {
-                if key.modifiers.contains(KeyModifiers::CONTROL) {
-                    self.delete_selected_entry();
-                }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.delete_selected_entry();
             }
             KeyCode::Char('/') => {
                 self.current_tab = AppTab::Search;
                 self.mode = AppMode::Insert;
                 self.search_query.clear();
             }
-            KeyCode::Char('s') => {
-                if key.modifiers.contains(KeyModifiers::CONTROL) {
-                    match self.save() {
-                        Ok(_) => self.add_message("Journal saved".to_string()),
-                        Err(e) => self.add_message(format!("Save failed: {}", e)),
-                    }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                match self.save() {
+                    Ok(_) => self.add_message("Journal saved".to_string()),
+                    Err(e) => self.add_message(format!("Save failed: {}", e)),
                 }
             }
             _ => {}
@@ -170,29 +696,46 @@ impl App {
                 self.input_mode = None;
             }
             KeyCode::Enter => {
-                if self.current_tab == AppTab::Search {
-                    self.search_query = self.input_buffer.clone();
-                    self.input_buffer.clear();
+                if self.current_tab == AppTab::Search && self.input_mode.is_none() {
                     self.mode = AppMode::Normal;
-                } else if let Some(input_mode) = &self.input_mode {
-                    self.create_entry(input_mode.clone())?;
-                    self.input_buffer.clear();
+                } else if let Some(input_mode) = self.input_mode.take() {
                     self.mode = AppMode::Normal;
-                    self.input_mode = None;
+                    let input = std::mem::take(&mut self.input_buffer);
+                    self.submit_input(input_mode, input)?;
                 }
             }
             KeyCode::Backspace => {
-                if self.current_tab == AppTab::Search {
+                if self.current_tab == AppTab::Search && self.input_mode.is_none() {
                     self.search_query.pop();
-                } else {
-                    self.input_buffer.pop();
+                } else if self.input_cursor > 0 {
+                    self.input_cursor -= 1;
+                    let index = self.input_byte_index();
+                    self.input_buffer.remove(index);
                 }
             }
+            KeyCode::Delete if self.input_cursor < self.input_buffer.chars().count() => {
+                let index = self.input_byte_index();
+                self.input_buffer.remove(index);
+            }
+            KeyCode::Left => {
+                self.input_cursor = self.input_cursor.saturating_sub(1);
+            }
+            KeyCode::Right => {
+                self.input_cursor = (self.input_cursor + 1).min(self.input_buffer.chars().count());
+            }
+            KeyCode::Home => {
+                self.input_cursor = 0;
+            }
+            KeyCode::End => {
+                self.input_cursor = self.input_buffer.chars().count();
+            }
             KeyCode::Char(c) => {
-                if self.current_tab == AppTab::Search {
+                if self.current_tab == AppTab::Search && self.input_mode.is_none() {
                     self.search_query.push(c);
                 } else {
-                    self.input_buffer.push(c);
+                    let index = self.input_byte_index();
+                    self.input_buffer.insert(index, c);
+                    self.input_cursor += 1;
                 }
             }
             _ => {}
@@ -200,102 +743,1083 @@ impl App {
         Ok(())
     }
 
-    fn handle_command_key(&mut self, _key: KeyEvent) -> Result<()> {
-        Ok(())
-    }
-
-    fn create_entry(&mut self, input_mode: InputMode) -> Result<()> {
-        if self.input_buffer.trim().is_empty() {
+    fn handle_migration_key(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(review) = self.migration.as_mut() else {
             return Ok(());
-        }
-
-        let bullet_type = match input_mode {
-            InputMode::Task => BulletType::Task,
-            InputMode::Event => BulletType::Event,
-            InputMode::Note => BulletType::Note,
-        };
-
-        let date = match self.current_tab {
-            AppTab::Daily => self.current_date,
-            AppTab::Monthly => self.current_date,
-            AppTab::Future => {
-                Local::now().date_naive().succ_opt().unwrap_or(Local::now().date_naive())
-            }
-            _ => self.current_date,
-        };
-
-        let entry = Entry::new(self.input_buffer.clone(), bullet_type, date);
-        self.journal.add_entry(entry);
-        
-        let type_name = match input_mode {
-            InputMode::Task => "Task",
-            InputMode::Event => "Event", 
-            InputMode::Note => "Note",
-        };
-        self.add_message(format!("{} added", type_name));
-        Ok(())
-    }
-
-    fn next_tab(&mut self) {
-        self.current_tab = match self.current_tab {
-            AppTab::Daily => AppTab::Monthly,
-            AppTab::Monthly => AppTab::Future,
-            AppTab::Future => AppTab::Collections,
-            AppTab::Collections => AppTab::Search,
-            AppTab::Search => AppTab::Daily,
-        };
-    }
-
-    fn prev_tab(&mut self) {
-        self.current_tab = match self.current_tab {
-            AppTab::Daily => AppTab::Search,
-            AppTab::Monthly => AppTab::Daily,
-            AppTab::Future => AppTab::Monthly,
-            AppTab::Collections => AppTab::Future,
-            AppTab::Search => AppTab::Collections,
         };
-    }
+        let today = Local::now().date_naive();
 
-    fn move_selection_down(&mut self) {
-        let entries = self.get_current_entries();
-        if let Some(selected) = self.selected_entry {
-            if selected < entries.len().saturating_sub(1) {
-                self.selected_entry = Some(selected + 1);
+        if let Some(input) = review.schedule_input.as_mut() {
+            match key.code {
+                KeyCode::Esc => review.schedule_input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Enter => {
+                    let input = review.schedule_input.take().unwrap_or_default();
+                    match parse_entry_date(&input) {
+                        Some((date, whole_month)) if date > today => {
+                            if let Some(id) = review.selected_task() {
+                                let scheduled = self.forward_task(id, None, |journal| {
+                                    journal.schedule_entry(id, date, whole_month)
+                                });
+                                if let Some(operation) = scheduled {
+                                    self.record(operation);
+                                    self.resolve_reviewed_task();
+                                    self.add_message(format!("Task scheduled for {}", input.trim()));
+                                } else {
+                                    self.add_message("Could not schedule the task".to_string());
+                                }
+                            }
+                        }
+                        Some(_) => self.add_message("Schedule for a day after today or a later month".to_string()),
+                        None => self.add_message(format!("Invalid date '{}', expected YYYY-MM-DD or YYYY-MM", input.trim())),
+                    }
+                }
+                _ => {}
             }
-        } else if !entries.is_empty() {
-            self.selected_entry = Some(0);
+            self.finish_migration_if_done();
+            return Ok(());
         }
-    }
 
-    fn move_selection_up(&mut self) {
-        if let Some(selected) = self.selected_entry {
-            if selected > 0 {
-                self.selected_entry = Some(selected - 1);
+        if let Some(picked) = review.collection_picker {
+            let collection_ids: Vec<Uuid> = self.journal.sorted_collections()
+                .iter()
+                .map(|collection| collection.id)
+                .collect();
+            match key.code {
+                KeyCode::Esc => review.collection_picker = None,
+                KeyCode::Char('j') | KeyCode::Down if picked + 1 < collection_ids.len() => {
+                    review.collection_picker = Some(picked + 1);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    review.collection_picker = Some(picked.saturating_sub(1));
+                }
+                KeyCode::Enter => {
+                    review.collection_picker = None;
+                    if let (Some(id), Some(&collection_id)) = (review.selected_task(), collection_ids.get(picked)) {
+                        let forwarded = self.forward_task(id, Some(collection_id), |journal| {
+                            journal.migrate_entry_to_collection(id, collection_id)
+                        });
+                        if let Some(operation) = forwarded {
+                            self.record(operation);
+                            self.resolve_reviewed_task();
+                            let name = self.journal.collections[&collection_id].name.clone();
+                            self.add_message(format!("Task moved to {}", name));
+                        } else {
+                            self.add_message("Could not move the task".to_string());
+                        }
+                    }
+                }
+                _ => {}
             }
+            self.finish_migration_if_done();
+            return Ok(());
         }
-    }
 
-    fn handle_left(&mut self) {
-        match self.current_tab {
-            AppTab::Daily => {
-                self.current_date = self.current_date.pred_opt()
-                    .unwrap_or(self.current_date);
-                self.selected_entry = None;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.migration = None;
             }
-            AppTab::Monthly => {
-                let (year, month) = self.selected_month;
-                if month == 1 {
-                    self.selected_month = (year - 1, 12);
+            KeyCode::Char('j') | KeyCode::Down if review.selected + 1 < review.tasks.len() => {
+                review.selected += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                review.selected = review.selected.saturating_sub(1);
+            }
+            KeyCode::Char('>') => {
+                if let Some(id) = review.selected_task() {
+                    review.resolve_selected();
+                    self.migrate_tasks(vec![id], today);
+                    self.add_message("Task migrated to today".to_string());
+                }
+            }
+            KeyCode::Char('<') => {
+                let first_of_next_month = NaiveDate::from_ymd_opt(today.year(), today.month(), 1)
+                    .and_then(|date| date.checked_add_months(chrono::Months::new(1)))
+                    .unwrap_or(today);
+                review.schedule_input = Some(first_of_next_month.format("%Y-%m").to_string());
+            }
+            KeyCode::Char('c') => {
+                if self.journal.collections.is_empty() {
+                    self.add_message("No collections to move tasks to".to_string());
                 } else {
-                    self.selected_month = (year, month - 1);
+                    review.collection_picker = Some(0);
                 }
-                self.selected_entry = None;
+            }
+            KeyCode::Char('x') => {
+                if let Some(id) = review.selected_task() {
+                    review.resolve_selected();
+                    self.change_entry(id, |entry| entry.set_status(TaskStatus::Irrelevant));
+                    self.add_message("Task struck as irrelevant".to_string());
+                }
+            }
+            KeyCode::Char('A') => {
+                let tasks = std::mem::take(&mut review.tasks);
+                let migrated = self.migrate_tasks(tasks, today);
+                self.add_message(format!("Migrated {} task(s) to today", migrated));
             }
             _ => {}
         }
+        self.finish_migration_if_done();
+        Ok(())
     }
 
-    fn handle_right(&mut self) {
+    fn finish_migration_if_done(&mut self) {
+        if self.migration.as_ref().is_some_and(|review| review.tasks.is_empty()) {
+            self.migration = None;
+            self.add_message("Migration review complete".to_string());
+        }
+    }
+
+    fn handle_command_key(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => {
+                self.command_line.clear();
+                self.mode = AppMode::Normal;
+            }
+            KeyCode::Enter => {
+                let input = self.command_line.submit();
+                self.mode = AppMode::Normal;
+                let today = Local::now().date_naive();
+                let result = parse_command(&input, today)
+                    .and_then(|command| self.execute_command(command));
+                if let Err(e) = result {
+                    self.add_message(format!("Error: {}", e));
+                }
+            }
+            KeyCode::Tab => {
+                let themes: Vec<&str> = ConfigManager::get_predefined_themes()
+                    .into_iter()
+                    .map(|(name, _)| name)
+                    .collect();
+                let journals = self.config.get_config().journal_names();
+                self.command_line.complete(&themes, &journals);
+            }
+            KeyCode::Up => self.command_line.history_prev(),
+            KeyCode::Down => self.command_line.history_next(),
+            KeyCode::Backspace => {
+                if self.command_line.buffer.is_empty() {
+                    self.mode = AppMode::Normal;
+                } else {
+                    self.command_line.pop();
+                }
+            }
+            KeyCode::Char(c) => self.command_line.push(c),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn execute_command(&mut self, command: Command) -> Result<()> {
+        match command {
+            Command::Write => {
+                self.save()?;
+                self.add_message("Journal saved".to_string());
+            }
+            Command::Quit => {
+                self.request_quit();
+            }
+            Command::ForceQuit => {
+                self.should_quit = true;
+            }
+            Command::WriteQuit => {
+                self.save()?;
+                self.should_quit = true;
+            }
+            Command::Help => {
+                self.show_help = true;
+            }
+            Command::Goto(date) => {
+                self.current_date = date;
+                self.selected_month = (date.year(), date.month());
+                self.current_tab = AppTab::Daily;
+                self.selected_entry = None;
+            }
+            Command::Migrate(from) => {
+                let to = self.current_date;
+                let from = match from {
+                    Some(from) => from,
+                    None => to.pred_opt().unwrap_or(to),
+                };
+                if from >= to {
+                    bail!("Can only migrate from an earlier day to {}", to);
+                }
+                let tasks: Vec<Uuid> = self.journal.entries_for_date(from)
+                    .iter()
+                    .filter(|entry| entry.status == Some(TaskStatus::Incomplete))
+                    .map(|entry| entry.id)
+                    .collect();
+                let migrated = self.migrate_tasks(tasks, to);
+                self.add_message(format!("Migrated {} task(s) from {} to {}", migrated, from, to));
+            }
+            Command::Journal(None) => {
+                self.open_journal_picker();
+            }
+            Command::Journal(Some(journal)) => {
+                self.switch_journal(&journal)?;
+            }
+            Command::Theme(name) => {
+                let known = ConfigManager::get_predefined_themes()
+                    .iter()
+                    .any(|(theme, _)| *theme == name);
+                if !known {
+                    bail!("Unknown theme: {}", name);
+                }
+                self.config.set_theme(&name)?;
+                self.add_message(format!("Theme set to {}", name));
+            }
+            Command::TagAdd(tag) => {
+                let id = self.selected_entry_id().ok_or_else(|| anyhow::anyhow!("No entry selected"))?;
+                self.change_entry(id, |entry| entry.add_tags([tag.clone()]));
+                self.add_message(format!("Tagged #{}", tag));
+            }
+            Command::TagRemove(tag) => {
+                let id = self.selected_entry_id().ok_or_else(|| anyhow::anyhow!("No entry selected"))?;
+                let removed = self.change_entry(id, |entry| {
+                    let before = entry.tags.len();
+                    entry.tags.retain(|t| t != &tag);
+                    entry.tags.len() != before
+                });
+                if removed != Some(true) {
+                    bail!("Entry is not tagged #{}", tag);
+                }
+                self.add_message(format!("Removed tag #{}", tag));
+            }
+            Command::CollectionNew(name) => {
+                if self.journal.collections.values().any(|c| c.name == name) {
+                    bail!("Collection '{}' already exists", name);
+                }
+                self.perform(Operation::AddCollection(Collection::new(name.clone(), None)));
+                self.add_message(format!("Collection '{}' created", name));
+            }
+            Command::SearchSave(name) => {
+                if !matches!(self.parsed_search(), Some(Ok(_))) {
+                    bail!("Type a valid query in the Search tab first");
+                }
+                self.save_search(name)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies an operation to the journal and records it for undo.
+    fn perform(&mut self, operation: Operation) {
+        operation.apply(&mut self.journal);
+        self.record(operation);
+    }
+
+    /// Records an operation that has already been applied to the journal.
+    fn record(&mut self, operation: Operation) {
+        self.history.record(operation);
+        self.mark_dirty();
+    }
+
+    /// Applies `change` to a copy of an entry and commits it as a single undoable edit.
+    fn change_entry<R>(&mut self, id: Uuid, change: impl FnOnce(&mut Entry) -> R) -> Option<R> {
+        let before = self.journal.get_entry(id)?.clone();
+        let mut after = before.clone();
+        let result = change(&mut after);
+        if after != before {
+            self.perform(Operation::UpdateEntry { before, after });
+        }
+        Some(result)
+    }
+
+    /// Takes the selected task off the review once it has been dealt with.
+    fn resolve_reviewed_task(&mut self) {
+        if let Some(review) = self.migration.as_mut() {
+            review.resolve_selected();
+        }
+    }
+
+    /// Runs one of the `Journal` migration methods on a task and returns the
+    /// change as an operation, leaving it to the caller to record.
+    fn forward_task(
+        &mut self,
+        id: Uuid,
+        collection: Option<Uuid>,
+        forward: impl FnOnce(&mut Journal) -> Option<Uuid>,
+    ) -> Option<Operation> {
+        let before = self.journal.get_entry(id)?.clone();
+        let new_id = forward(&mut self.journal)?;
+        let after = self.journal.get_entry(id)?.clone();
+        let entry = self.journal.get_entry(new_id)?.clone();
+        Some(Operation::Batch(vec![
+            Operation::UpdateEntry { before, after },
+            Operation::AddEntry { entry, collection },
+        ]))
+    }
+
+    /// Migrates tasks to `date` as one undoable step, returning how many were migrated.
+    fn migrate_tasks(&mut self, tasks: Vec<Uuid>, date: NaiveDate) -> usize {
+        let operations: Vec<Operation> = tasks
+            .into_iter()
+            .filter_map(|id| self.forward_task(id, None, |journal| journal.migrate_entry(id, date)))
+            .collect();
+        let migrated = operations.len();
+        if migrated > 0 {
+            self.record(Operation::Batch(operations));
+        }
+        migrated
+    }
+
+    fn undo(&mut self) {
+        let done = self.history.undo(&mut self.journal).map(|operation| format!("Undid {}", operation.describe()));
+        let Some(message) = done else {
+            self.add_message("Nothing to undo".to_string());
+            return;
+        };
+        self.mark_dirty();
+        self.clamp_selection();
+        self.add_message(message);
+    }
+
+    fn redo(&mut self) {
+        let done = self.history.redo(&mut self.journal).map(|operation| format!("Redid {}", operation.describe()));
+        let Some(message) = done else {
+            self.add_message("Nothing to redo".to_string());
+            return;
+        };
+        self.mark_dirty();
+        self.clamp_selection();
+        self.add_message(message);
+    }
+
+    fn selected_entry_id(&self) -> Option<Uuid> {
+        let selected = self.selected_entry?;
+        Some(self.get_current_entries().get(selected)?.id)
+    }
+
+    /// Handles keys specific to the collection list pane, returning whether the key was consumed.
+    fn handle_collection_list_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('a') => {
+                self.start_input(InputMode::CollectionName, String::new());
+            }
+            KeyCode::Char('r') => {
+                if let Some(collection) = self.selected_collection() {
+                    let name = collection.name.clone();
+                    self.start_input(InputMode::CollectionRename, name);
+                } else if let Some(search) = self.selected_saved_search() {
+                    let name = search.name.clone();
+                    self.start_input(InputMode::SavedSearchRename, name);
+                }
+            }
+            KeyCode::Char('i') => {
+                if let Some(collection) = self.selected_collection() {
+                    let description = collection.description.clone().unwrap_or_default();
+                    self.start_input(InputMode::CollectionDescription, description);
+                } else if let Some(search) = self.selected_saved_search() {
+                    let query = search.query.clone();
+                    self.start_input(InputMode::SavedSearchQuery, query);
+                }
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.delete_selected_collection();
+            }
+            KeyCode::Enter => {
+                if self.selected_collection().is_some() || self.selected_saved_search().is_some() {
+                    self.collection_focus = PaneFocus::Entries;
+                    self.selected_entry = None;
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn start_input(&mut self, input_mode: InputMode, initial: String) {
+        self.mode = AppMode::Insert;
+        self.input_mode = Some(input_mode);
+        self.input_cursor = initial.chars().count();
+        self.input_buffer = initial;
+    }
+
+    /// Byte offset in `input_buffer` of the character the cursor sits on.
+    fn input_byte_index(&self) -> usize {
+        self.input_buffer
+            .char_indices()
+            .nth(self.input_cursor)
+            .map_or(self.input_buffer.len(), |(index, _)| index)
+    }
+
+    fn submit_input(&mut self, input_mode: InputMode, input: String) -> Result<()> {
+        match input_mode {
+            InputMode::Task => self.create_entry(BulletType::Task, input),
+            InputMode::Event => self.create_entry(BulletType::Event, input),
+            InputMode::Note => self.create_entry(BulletType::Note, input),
+            InputMode::CollectionName => self.create_collection(input),
+            InputMode::CollectionRename => self.rename_selected_collection(input),
+            InputMode::CollectionDescription => self.describe_selected_collection(input),
+            InputMode::SavedSearchName => self.save_search(input),
+            InputMode::SavedSearchRename => self.rename_selected_saved_search(input),
+            InputMode::SavedSearchQuery => self.change_selected_saved_query(input),
+            InputMode::EditEntry(id) => self.update_entry_content(id, input),
+            InputMode::EditDate(id) => self.update_entry_date(id, input),
+        }
+    }
+
+    fn update_entry_content(&mut self, id: Uuid, content: String) -> Result<()> {
+        if content.trim().is_empty() {
+            self.add_message("Entry content cannot be empty".to_string());
+            return Ok(());
+        }
+        let (signifiers, content) = parse_signifiers(&content);
+        let content = content.to_string();
+        let updated = self.change_entry(id, |entry| {
+            entry.set_content(content);
+            signifiers.apply(entry);
+        });
+        if updated.is_some() {
+            self.add_message("Entry updated".to_string());
+        }
+        Ok(())
+    }
+
+    fn update_entry_date(&mut self, id: Uuid, input: String) -> Result<()> {
+        match parse_entry_date(&input) {
+            Some((date, whole_month)) => self.move_entry(id, date, whole_month),
+            None => self.add_message(format!("Invalid date '{}', expected YYYY-MM-DD or YYYY-MM", input.trim())),
+        }
+        Ok(())
+    }
+
+    fn move_entry(&mut self, id: Uuid, date: NaiveDate, whole_month: bool) {
+        let moved = self.change_entry(id, |entry| {
+            entry.set_date(date, whole_month);
+            entry.date_label()
+        });
+        if let Some(label) = moved {
+            self.clamp_selection();
+            self.add_message(format!("Entry moved to {}", label));
+        }
+    }
+
+    fn cycle_selected_bullet_type(&mut self) {
+        let Some(id) = self.selected_entry_id() else {
+            return;
+        };
+        let changed = self.change_entry(id, |entry| {
+            let bullet_type = match entry.bullet_type {
+                BulletType::Task => BulletType::Event,
+                BulletType::Event => BulletType::Note,
+                BulletType::Note => BulletType::Task,
+            };
+            entry.set_bullet_type(bullet_type);
+            bullet_type
+        });
+        if let Some(bullet_type) = changed {
+            self.add_message(format!("Entry changed to {:?}", bullet_type));
+        }
+    }
+
+    /// Moves the selected entry by days, or by months when it spans a whole month.
+    fn shift_selected_entry_date(&mut self, days: i64) {
+        let Some(entry) = self.selected_entry_id().and_then(|id| self.journal.get_entry(id)) else {
+            return;
+        };
+        let (id, whole_month) = (entry.id, entry.whole_month);
+        let date = if whole_month {
+            let months = chrono::Months::new(days.unsigned_abs() as u32);
+            if days < 0 { entry.date.checked_sub_months(months) } else { entry.date.checked_add_months(months) }
+        } else {
+            entry.date.checked_add_signed(chrono::Duration::days(days))
+        };
+        if let Some(date) = date {
+            self.move_entry(id, date, whole_month);
+        }
+    }
+
+    fn set_selected_status(&mut self, status: TaskStatus) {
+        let Some(id) = self.selected_entry_id() else {
+            return;
+        };
+        let message = match self.change_entry(id, |entry| entry.set_status(status)) {
+            Some(Ok(())) => format!("Task marked {}", status.label()),
+            Some(Err(e)) => e.to_string(),
+            None => return,
+        };
+        self.add_message(message);
+    }
+
+    fn show_selected_status_history(&mut self) {
+        let Some(entry) = self.selected_entry_id().and_then(|id| self.journal.get_entry(id)) else {
+            return;
+        };
+        let message = if entry.bullet_type != BulletType::Task {
+            "Only tasks have a status history".to_string()
+        } else if entry.status_history.is_empty() {
+            "No status changes recorded".to_string()
+        } else {
+            let changes: Vec<String> = entry.status_history
+                .iter()
+                .map(|change| format!("{} {}", change.status.label(), change.changed_at.format("%Y-%m-%d %H:%M")))
+                .collect();
+            format!("History: {}", changes.join(", "))
+        };
+        self.add_message(message);
+    }
+
+    fn adjust_selected_priority(&mut self, raise: bool) {
+        let Some(id) = self.selected_entry_id() else {
+            return;
+        };
+        let priority = self.change_entry(id, |entry| {
+            if raise {
+                entry.raise_priority();
+            } else {
+                entry.lower_priority();
+            }
+            entry.priority
+        });
+        let message = match priority {
+            Some(Some(priority)) => format!("Priority set to {}", priority),
+            Some(None) => "Priority cleared".to_string(),
+            None => return,
+        };
+        self.follow_entry(id);
+        self.add_message(message);
+    }
+
+    fn toggle_selected_inspiration(&mut self) {
+        let Some(id) = self.selected_entry_id() else {
+            return;
+        };
+        let inspiration = self.change_entry(id, |entry| {
+            entry.inspiration = !entry.inspiration;
+            entry.inspiration
+        });
+        match inspiration {
+            Some(true) => self.add_message("Marked as inspiration".to_string()),
+            Some(false) => self.add_message("Inspiration mark removed".to_string()),
+            None => {}
+        }
+    }
+
+    /// Steps the priority filter through off, 1+, 2+ ... up to the highest priority.
+    fn cycle_priority_filter(&mut self) {
+        self.min_priority = match self.min_priority {
+            None => Some(1),
+            Some(min) if min < MAX_PRIORITY => Some(min + 1),
+            Some(_) => None,
+        };
+        self.selected_entry = None;
+        self.add_message(match self.min_priority {
+            Some(min) => format!("Showing entries with priority {} or higher", min),
+            None => "Showing entries of any priority".to_string(),
+        });
+    }
+
+    /// Keeps the selection on the entry after sorting moved it, or inside the list if it left.
+    fn follow_entry(&mut self, id: Uuid) {
+        match self.get_current_entries().iter().position(|entry| entry.id == id) {
+            Some(index) => self.selected_entry = Some(index),
+            None => self.clamp_selection(),
+        }
+    }
+
+    /// Applies the priority sort and filter of the daily, monthly and future views.
+    fn arrange<'a>(&self, mut entries: Vec<&'a Entry>) -> Vec<&'a Entry> {
+        if let Some(min) = self.min_priority {
+            entries.retain(|entry| entry.priority.is_some_and(|priority| priority >= min));
+        }
+        if self.sort_by_priority {
+            entries.sort_by_key(|entry| Reverse(entry.priority));
+        }
+        entries
+    }
+
+    /// A month's entries as the monthly and future logs list them, those planned
+    /// for the whole month first.
+    pub fn month_log(&self, (year, month): (i32, u32)) -> Vec<&Entry> {
+        let mut entries = self.arrange(self.journal.entries_for_month(year, month));
+        entries.sort_by_key(|entry| !entry.whole_month);
+        entries
+    }
+
+    /// The months of the Future Log grid, from next month on, scrolled far
+    /// enough to include the selected one.
+    pub fn future_grid(&self) -> Vec<(i32, u32)> {
+        let today = Local::now().date_naive();
+        let mut first = shift_month((today.year(), today.month()), 1);
+        let shown = self.future_months_shown as i32;
+        while self.future_month >= shift_month(first, shown) {
+            first = shift_month(first, shown);
+        }
+        (0..shown).map(|offset| shift_month(first, offset)).collect()
+    }
+
+    /// Describes the active sort and filter for list titles, or nothing when neither is on.
+    pub fn arrangement_label(&self) -> String {
+        let mut label = String::new();
+        if self.sort_by_priority {
+            label.push_str(" - by priority");
+        }
+        if let Some(min) = self.min_priority {
+            label.push_str(&format!(" - priority {}+", min));
+        }
+        label
+    }
+
+    /// Keeps the selection inside the current list after entries leave it.
+    fn clamp_selection(&mut self) {
+        match self.current_tab {
+            AppTab::Search => self.refresh_search(),
+            AppTab::Collections => self.refresh_saved_searches(),
+            _ => {}
+        }
+        let count = self.get_current_entries().len();
+        self.selected_entry = match self.selected_entry {
+            _ if count == 0 => None,
+            Some(selected) => Some(selected.min(count - 1)),
+            None => None,
+        };
+        let tags = self.journal.tag_counts().len();
+        self.selected_tag = match self.selected_tag {
+            _ if tags == 0 => None,
+            selected => selected.map(|selected| selected.min(tags - 1)),
+        };
+    }
+
+    pub fn selected_tag(&self) -> Option<&str> {
+        let selected = self.selected_tag?;
+        self.journal.tag_counts().get(selected).map(|(tag, _)| *tag)
+    }
+
+    /// Re-runs the search after the query or the journal changed.
+    fn refresh_search(&mut self) {
+        self.search_hits = match self.parsed_search() {
+            Some(Ok(query)) => self.hits(&query),
+            _ => Vec::new(),
+        };
+    }
+
+    /// Re-runs every saved search after one of them or the journal changed.
+    fn refresh_saved_searches(&mut self) {
+        self.saved_search_hits = self.journal.saved_searches
+            .values()
+            .map(|search| {
+                let hits = Query::parse(&search.query).map(|query| self.hits(&query)).map_err(|e| e.to_string());
+                (search.id, hits)
+            })
+            .collect();
+    }
+
+    fn hits(&self, query: &Query) -> Vec<SearchHit> {
+        self.journal
+            .search_entries(query)
+            .into_iter()
+            .map(|(entry, relevance)| SearchHit { id: entry.id, highlights: relevance.highlights })
+            .collect()
+    }
+
+    /// The current search results with the content bytes each one matched.
+    pub fn search_results(&self) -> Vec<FoundEntry<'_>> {
+        self.hit_entries(&self.search_hits)
+    }
+
+    fn hit_entries<'a>(&'a self, hits: &'a [SearchHit]) -> Vec<FoundEntry<'a>> {
+        hits.iter()
+            .filter_map(|hit| Some((self.journal.get_entry(hit.id)?, hit.highlights.as_slice())))
+            .collect()
+    }
+
+    /// Shows the selected search result in its daily log, remembering it for
+    /// `return_to_search`. Entries planned for a whole month open in the
+    /// monthly log, or the future log until that month comes.
+    fn open_selected_result(&mut self) {
+        let Some(entry) = self.selected_entry_id().and_then(|id| self.journal.get_entry(id)) else {
+            return;
+        };
+        let (id, date, whole_month, priority) = (entry.id, entry.date, entry.whole_month, entry.priority.unwrap_or(0));
+        if self.min_priority.is_some_and(|min| priority < min) {
+            self.min_priority = None;
+            self.add_message("Priority filter cleared to show the entry".to_string());
+        }
+        let month = (date.year(), date.month());
+        let today = Local::now().date_naive();
+        self.current_tab = match whole_month {
+            false => AppTab::Daily,
+            true if month > (today.year(), today.month()) => AppTab::Future,
+            true => AppTab::Monthly,
+        };
+        match self.current_tab {
+            AppTab::Daily => self.current_date = date,
+            AppTab::Future => self.future_month = month,
+            _ => {}
+        }
+        self.selected_month = month;
+        self.selected_entry = self.get_current_entries().iter().position(|entry| entry.id == id);
+        self.search_return = Some((self.current_tab.clone(), id));
+    }
+
+    /// A log's title, led by the search it was opened from while Backspace returns there.
+    pub fn log_title(&self, title: &str) -> String {
+        match self.search_return {
+            Some(_) => format!("Search \"{}\" › {} (Backspace to return)", self.search_query, title),
+            None => title.to_string(),
+        }
+    }
+
+    /// Goes back to the search results the log was opened from.
+    fn return_to_search(&mut self) {
+        let Some((_, id)) = self.search_return.take() else {
+            return;
+        };
+        self.current_tab = AppTab::Search;
+        self.refresh_search();
+        self.selected_entry = self.search_hits
+            .iter()
+            .position(|hit| hit.id == id)
+            .or((!self.search_hits.is_empty()).then_some(0));
+    }
+
+    /// The search box parsed as a query, or `None` while it is blank.
+    pub fn parsed_search(&self) -> Option<Result<Query>> {
+        if self.search_query.trim().is_empty() {
+            None
+        } else {
+            Some(Query::parse(&self.search_query))
+        }
+    }
+
+    fn create_entry(&mut self, bullet_type: BulletType, content: String) -> Result<()> {
+        if content.trim().is_empty() {
+            return Ok(());
+        }
+
+        let date = match self.current_tab {
+            AppTab::Daily => self.current_date,
+            AppTab::Monthly => self.current_date,
+            AppTab::Future => {
+                let (year, month) = self.future_month;
+                NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(self.current_date)
+            }
+            _ => self.current_date,
+        };
+
+        let mut entry = Entry::from_input(&content, bullet_type, date);
+        if self.current_tab == AppTab::Future {
+            entry.set_date(date, true);
+        }
+        let type_name = match bullet_type {
+            BulletType::Task => "Task",
+            BulletType::Event => "Event",
+            BulletType::Note => "Note",
+        };
+
+        if self.current_tab == AppTab::Collections {
+            let Some(id) = self.selected_collection().map(|collection| collection.id) else {
+                self.add_message("Select a collection first".to_string());
+                return Ok(());
+            };
+            if let Some(name) = self.journal.collections.get(&id).map(|c| c.name.clone()) {
+                self.perform(Operation::AddEntry { entry, collection: Some(id) });
+                self.collection_focus = PaneFocus::Entries;
+                self.add_message(format!("{} added to {}", type_name, name));
+            }
+            return Ok(());
+        }
+
+        let planned = entry.whole_month.then(|| entry.date.format("%B %Y").to_string());
+        self.perform(Operation::AddEntry { entry, collection: None });
+        match planned {
+            Some(month) => self.add_message(format!("{} planned for {}", type_name, month)),
+            None => self.add_message(format!("{} added", type_name)),
+        }
+        Ok(())
+    }
+
+    fn create_collection(&mut self, name: String) -> Result<()> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Ok(());
+        }
+        if self.journal.collections.values().any(|c| c.name == name) {
+            self.add_message(format!("Collection '{}' already exists", name));
+            return Ok(());
+        }
+
+        let collection = Collection::new(name.clone(), None);
+        let id = collection.id;
+        self.perform(Operation::AddCollection(collection));
+        self.selected_collection = self.journal.sorted_collections()
+            .iter()
+            .position(|collection| collection.id == id);
+        self.add_message(format!("Collection '{}' created", name));
+
+        // Follow up with an optional description; an empty one is skipped.
+        self.start_input(InputMode::CollectionDescription, String::new());
+        Ok(())
+    }
+
+    fn rename_selected_collection(&mut self, name: String) -> Result<()> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Ok(());
+        }
+        let Some(id) = self.selected_collection().map(|collection| collection.id) else {
+            return Ok(());
+        };
+        if self.journal.collections.values().any(|c| c.name == name && c.id != id) {
+            self.add_message(format!("Collection '{}' already exists", name));
+            return Ok(());
+        }
+        if self.change_collection(id, |collection| collection.name = name.clone()) {
+            self.add_message(format!("Collection renamed to '{}'", name));
+        }
+        Ok(())
+    }
+
+    fn describe_selected_collection(&mut self, description: String) -> Result<()> {
+        let Some(id) = self.selected_collection().map(|collection| collection.id) else {
+            return Ok(());
+        };
+        let description = description.trim();
+        let description = if description.is_empty() {
+            None
+        } else {
+            Some(description.to_string())
+        };
+        self.change_collection(id, |collection| collection.description = description);
+        Ok(())
+    }
+
+    /// Applies `change` to a copy of a collection and commits it as a single undoable edit.
+    fn change_collection(&mut self, id: Uuid, change: impl FnOnce(&mut Collection)) -> bool {
+        let Some(before) = self.journal.collections.get(&id).cloned() else {
+            return false;
+        };
+        let mut after = before.clone();
+        change(&mut after);
+        if after != before {
+            self.perform(Operation::UpdateCollection { before, after });
+        }
+        true
+    }
+
+    /// Deletes the selected collection or saved search.
+    fn delete_selected_collection(&mut self) {
+        if let Some(collection) = self.selected_collection() {
+            let collection = collection.clone();
+            let message = format!("Collection '{}' deleted", collection.name);
+            self.perform(Operation::DeleteCollection(collection));
+            self.add_message(message);
+        } else if let Some(search) = self.selected_saved_search() {
+            let search = search.clone();
+            let message = format!("Saved search '{}' deleted", search.name);
+            self.perform(Operation::DeleteSavedSearch(search));
+            self.add_message(message);
+        } else {
+            return;
+        }
+        let count = self.collection_list_len();
+        self.selected_collection = match self.selected_collection {
+            _ if count == 0 => None,
+            Some(selected) => Some(selected.min(count - 1)),
+            None => None,
+        };
+    }
+
+    /// Saves the search box query under `name`, to be listed with the collections.
+    fn save_search(&mut self, name: String) -> Result<()> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Ok(());
+        }
+        if self.journal.saved_search_named(&name).is_some() {
+            self.add_message(format!("Saved search '{}' already exists", name));
+            return Ok(());
+        }
+        let search = SavedSearch::new(name.clone(), self.search_query.trim().to_string());
+        self.perform(Operation::AddSavedSearch(search));
+        self.add_message(format!("Search saved as '{}', listed under Collections", name));
+        Ok(())
+    }
+
+    fn rename_selected_saved_search(&mut self, name: String) -> Result<()> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Ok(());
+        }
+        let Some(id) = self.selected_saved_search().map(|search| search.id) else {
+            return Ok(());
+        };
+        if self.journal.saved_searches.values().any(|s| s.name == name && s.id != id) {
+            self.add_message(format!("Saved search '{}' already exists", name));
+            return Ok(());
+        }
+        if self.change_saved_search(id, |search| search.name = name.clone()) {
+            self.add_message(format!("Saved search renamed to '{}'", name));
+        }
+        Ok(())
+    }
+
+    fn change_selected_saved_query(&mut self, query: String) -> Result<()> {
+        let query = query.trim().to_string();
+        let Some(id) = self.selected_saved_search().map(|search| search.id) else {
+            return Ok(());
+        };
+        if let Err(e) = Query::parse(&query) {
+            self.add_message(format!("Query not saved: {}", e));
+            return Ok(());
+        }
+        if !query.is_empty() && self.change_saved_search(id, |search| search.query = query) {
+            self.add_message("Saved search updated".to_string());
+        }
+        Ok(())
+    }
+
+    /// Applies `change` to a copy of a saved search and commits it as a single undoable edit.
+    fn change_saved_search(&mut self, id: Uuid, change: impl FnOnce(&mut SavedSearch)) -> bool {
+        let Some(before) = self.journal.saved_searches.get(&id).cloned() else {
+            return false;
+        };
+        let mut after = before.clone();
+        change(&mut after);
+        if after != before {
+            self.perform(Operation::UpdateSavedSearch { before, after });
+        }
+        true
+    }
+
+    pub fn selected_collection(&self) -> Option<&Collection> {
+        let selected = self.selected_collection?;
+        self.journal.sorted_collections().get(selected).copied()
+    }
+
+    /// The saved search selected in the Collections tab, listed after the collections.
+    pub fn selected_saved_search(&self) -> Option<&SavedSearch> {
+        let selected = self.selected_collection?.checked_sub(self.journal.collections.len())?;
+        self.journal.sorted_saved_searches().get(selected).copied()
+    }
+
+    /// Collections and saved searches in the Collections tab list.
+    fn collection_list_len(&self) -> usize {
+        self.journal.collections.len() + self.journal.saved_searches.len()
+    }
+
+    /// Results of a saved search as last refreshed, or why its query does not parse.
+    pub fn saved_search_results(&self, search: &SavedSearch) -> Result<Vec<FoundEntry<'_>>, &str> {
+        match self.saved_search_hits.get(&search.id) {
+            Some(Ok(hits)) => Ok(self.hit_entries(hits)),
+            Some(Err(e)) => Err(e),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn next_tab(&mut self) {
+        self.current_tab = match self.current_tab {
+            AppTab::Daily => AppTab::Monthly,
+            AppTab::Monthly => AppTab::Future,
+            AppTab::Future => AppTab::Collections,
+            AppTab::Collections => AppTab::Search,
+            AppTab::Search => AppTab::Tags,
+            AppTab::Tags => AppTab::Daily,
+        };
+    }
+
+    fn prev_tab(&mut self) {
+        self.current_tab = match self.current_tab {
+            AppTab::Daily => AppTab::Tags,
+            AppTab::Monthly => AppTab::Daily,
+            AppTab::Future => AppTab::Monthly,
+            AppTab::Collections => AppTab::Future,
+            AppTab::Search => AppTab::Collections,
+            AppTab::Tags => AppTab::Search,
+        };
+    }
+
+    fn move_selection_down(&mut self) {
+        if self.current_tab == AppTab::Collections && self.collection_focus == PaneFocus::List {
+            let count = self.collection_list_len();
+            self.selected_collection = match self.selected_collection {
+                Some(selected) if selected + 1 < count => Some(selected + 1),
+                None if count > 0 => Some(0),
+                other => other,
+            };
+            return;
+        }
+
+        if self.current_tab == AppTab::Tags && self.tag_focus == PaneFocus::List {
+            let count = self.journal.tag_counts().len();
+            self.selected_tag = match self.selected_tag {
+                Some(selected) if selected + 1 < count => Some(selected + 1),
+                None if count > 0 => Some(0),
+                other => other,
+            };
+            return;
+        }
+
+        let entries = self.get_current_entries();
+        if let Some(selected) = self.selected_entry {
+            if selected < entries.len().saturating_sub(1) {
+                self.selected_entry = Some(selected + 1);
+            }
+        } else if !entries.is_empty() {
+            self.selected_entry = Some(0);
+        }
+    }
+
+    fn move_selection_up(&mut self) {
+        if self.current_tab == AppTab::Collections && self.collection_focus == PaneFocus::List {
+            if let Some(selected) = self.selected_collection {
+                self.selected_collection = Some(selected.saturating_sub(1));
+            }
+            return;
+        }
+
+        if self.current_tab == AppTab::Tags && self.tag_focus == PaneFocus::List {
+            if let Some(selected) = self.selected_tag {
+                self.selected_tag = Some(selected.saturating_sub(1));
+            }
+            return;
+        }
+
+        if let Some(selected) = self.selected_entry {
+            if selected > 0 {
+                self.selected_entry = Some(selected - 1);
+            }
+        }
+    }
+
+    fn handle_left(&mut self) {
+        match self.current_tab {
+            AppTab::Daily => {
+                self.current_date = self.current_date.pred_opt()
+                    .unwrap_or(self.current_date);
+                self.selected_entry = None;
+            }
+            AppTab::Monthly => {
+                let (year, month) = self.selected_month;
+                if month == 1 {
+                    self.selected_month = (year - 1, 12);
+                } else {
+                    self.selected_month = (year, month - 1);
+                }
+                self.selected_entry = None;
+            }
+            AppTab::Future => {
+                let today = Local::now().date_naive();
+                let first = shift_month((today.year(), today.month()), 1);
+                self.future_month = shift_month(self.future_month, -1).max(first);
+                self.selected_entry = None;
+            }
+            AppTab::Collections => {
+                self.collection_focus = PaneFocus::List;
+                self.selected_entry = None;
+            }
+            AppTab::Tags => {
+                self.tag_focus = PaneFocus::List;
+                self.selected_entry = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_right(&mut self) {
         match self.current_tab {
             AppTab::Daily => {
                 self.current_date = self.current_date.succ_opt()
@@ -311,61 +1835,406 @@ impl App {
                 }
                 self.selected_entry = None;
             }
+            AppTab::Future => {
+                self.future_month = shift_month(self.future_month, 1);
+                self.selected_entry = None;
+            }
+            AppTab::Collections if self.selected_collection().is_some() || self.selected_saved_search().is_some() => {
+                self.collection_focus = PaneFocus::Entries;
+            }
+            AppTab::Tags if self.selected_tag().is_some() => {
+                self.tag_focus = PaneFocus::Entries;
+                self.selected_entry = None;
+            }
             _ => {}
         }
     }
 
     fn toggle_selected_entry(&mut self) {
-        if let Some(selected) = self.selected_entry {
-            let entries = self.get_current_entries();
-            if let Some(entry) = entries.get(selected) {
-                if let Some(entry_mut) = self.journal.get_entry_mut(entry.id) {
-                    entry_mut.toggle_complete();
-                    let status = if entry_mut.status.as_ref().unwrap_or(&crate::models::TaskStatus::Incomplete) 
-                        == &crate::models::TaskStatus::Complete {
-                        "completed"
-                    } else {
-                        "marked incomplete"
-                    };
-                    self.add_message(format!("Task {}", status));
-                }
+        if let Some(id) = self.selected_entry_id() {
+            let toggled = self.change_entry(id, |entry| {
+                entry.toggle_complete();
+                entry.status
+            });
+            if let Some(status) = toggled {
+                let status = if status.unwrap_or(TaskStatus::Incomplete) == TaskStatus::Complete {
+                    "completed"
+                } else {
+                    "marked incomplete"
+                };
+                self.add_message(format!("Task {}", status));
             }
         }
     }
 
     fn delete_selected_entry(&mut self) {
-        if let Some(selected) = self.selected_entry {
-            let entries = self.get_current_entries();
-            if let Some(entry) = entries.get(selected) {
-                self.journal.remove_entry(entry.id);
-                self.add_message("Entry deleted".to_string());
-                self.selected_entry = None;
-            }
+        let Some(id) = self.selected_entry_id() else {
+            return;
+        };
+        let collection = match self.current_tab {
+            AppTab::Collections => self.selected_collection().map(|collection| collection.id),
+            _ => None,
+        };
+        let entries = match collection {
+            Some(collection_id) => self.journal.collections[&collection_id].entries.as_slice(),
+            None => self.journal.entries(),
+        };
+        if let Some(index) = entries.iter().position(|entry| entry.id == id) {
+            let entry = entries[index].clone();
+            self.perform(Operation::DeleteEntry { entry, collection, index });
+            self.add_message("Entry deleted (u to undo)".to_string());
+            self.selected_entry = None;
         }
     }
 
-    fn get_current_entries(&self) -> Vec<&Entry> {
+    /// The entries listed by the current view, in the order shown.
+    pub fn get_current_entries(&self) -> Vec<&Entry> {
         match self.current_tab {
-            AppTab::Daily => self.journal.entries_for_date(self.current_date),
-            AppTab::Monthly => {
-                let (year, month) = self.selected_month;
-                self.journal.entries_for_month(year, month)
-            }
-            AppTab::Search => {
-                if self.search_query.is_empty() {
-                    Vec::new()
-                } else {
-                    self.journal.search_entries(&self.search_query)
-                }
-            }
-            AppTab::Future => {
-                let today = Local::now().date_naive();
-                self.journal.entries.iter()
-                    .filter(|entry| entry.date > today)
-                    .collect()
-            }
-            AppTab::Collections => Vec::new(),
+            AppTab::Daily => self.arrange(self.journal.entries_for_date(self.current_date)),
+            AppTab::Monthly => self.month_log(self.selected_month),
+            AppTab::Search => self.search_results().into_iter().map(|(entry, _)| entry).collect(),
+            AppTab::Future => self.month_log(self.future_month),
+            AppTab::Collections => match (&self.collection_focus, self.selected_collection(), self.selected_saved_search()) {
+                (PaneFocus::Entries, Some(collection), _) => collection.entries.iter().collect(),
+                (PaneFocus::Entries, None, Some(search)) => self.saved_search_results(search)
+                    .map(|found| found.into_iter().map(|(entry, _)| entry).collect())
+                    .unwrap_or_default(),
+                _ => Vec::new(),
+            },
+            AppTab::Tags => match (&self.tag_focus, self.selected_tag()) {
+                (PaneFocus::Entries, Some(tag)) => self.journal.entries_with_tag(tag),
+                _ => Vec::new(),
+            },
+        }
+    }
+
+}
+
+/// The month `offset` months after (or before) the given one.
+pub fn shift_month((year, month): (i32, u32), offset: i32) -> (i32, u32) {
+    let index = year * 12 + month as i32 - 1 + offset;
+    (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StorageBackend;
+
+    fn open_app(test: &str, entries: Vec<Entry>) -> (App, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(format!("bujo-app-{}-{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let location = JournalLocation { name: None, dir: dir.join("data"), backend: StorageBackend::Json };
+        let mut journal = Journal::default();
+        for entry in entries {
+            journal.add_entry(entry);
         }
+        Storage::new(&location).unwrap().save_journal(&journal).unwrap();
+        let app = App::open(ConfigManager::in_dir(dir.join("config")).unwrap(), location).unwrap();
+        (app, dir)
     }
 
-}
\ No newline at end of file
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE)).unwrap();
+    }
+
+    fn search(app: &mut App, query: &str) {
+        app.current_tab = AppTab::Search;
+        app.search_query = query.to_string();
+        app.refresh_search();
+        app.selected_entry = Some(0);
+    }
+
+    #[test]
+    fn whole_month_results_open_in_their_month() {
+        let today = Local::now().date_naive();
+        let this_month = NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap();
+        let next_month = this_month.checked_add_months(chrono::Months::new(2)).unwrap();
+        let mut current = Entry::new("Pay the gym".to_string(), BulletType::Task, this_month);
+        current.set_date(this_month, true);
+        let mut planned = Entry::new("Renew passport".to_string(), BulletType::Task, next_month);
+        planned.set_date(next_month, true);
+        let (current_id, planned_id) = (current.id, planned.id);
+        let (mut app, dir) = open_app("whole-month", vec![current, planned]);
+
+        search(&mut app, "passport");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_tab, AppTab::Future);
+        assert_eq!(app.future_month, (next_month.year(), next_month.month()));
+        assert_eq!(app.selected_entry_id(), Some(planned_id));
+
+        press(&mut app, KeyCode::Backspace);
+        assert_eq!(app.current_tab, AppTab::Search);
+        assert_eq!(app.selected_entry_id(), Some(planned_id));
+
+        search(&mut app, "gym");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.current_tab, AppTab::Monthly);
+        assert_eq!(app.selected_month, (today.year(), today.month()));
+        assert_eq!(app.selected_entry_id(), Some(current_id));
+        assert!(app.search_return.is_some());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn undo_and_redo_with_nothing_to_do_leave_the_journal_clean() {
+        let (mut app, dir) = open_app("undo", Vec::new());
+        press(&mut app, KeyCode::Char('u'));
+        app.handle_key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL)).unwrap();
+        assert!(!app.dirty);
+        assert_eq!(app.messages.back().map(String::as_str), Some("Nothing to redo"));
+
+        app.create_entry(BulletType::Task, "Water the plants".to_string()).unwrap();
+        app.dirty = false;
+        press(&mut app, KeyCode::Char('u'));
+        assert!(app.dirty && app.journal.entries().is_empty());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reviewed_tasks_stay_in_the_review_until_they_are_scheduled() {
+        let today = Local::now().date_naive();
+        let overdue = Entry::new("Call the bank".to_string(), BulletType::Task, today.pred_opt().unwrap());
+        let id = overdue.id;
+        let (mut app, dir) = open_app("review", vec![overdue]);
+        app.open_migration_review();
+
+        // Completed elsewhere while the review was open.
+        app.change_entry(id, |entry| entry.set_status(TaskStatus::Complete));
+        press(&mut app, KeyCode::Char('<'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.migration.as_ref().map(|review| review.tasks.clone()), Some(vec![id]));
+        assert_eq!(app.messages.back().map(String::as_str), Some("Could not schedule the task"));
+
+        app.change_entry(id, |entry| entry.set_status(TaskStatus::Incomplete));
+        press(&mut app, KeyCode::Char('<'));
+        press(&mut app, KeyCode::Enter);
+        assert!(app.migration.is_none());
+        assert_eq!(app.journal.get_entry(id).and_then(|entry| entry.status), Some(TaskStatus::Scheduled));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn saved_search_results_refresh_as_keys_are_handled() {
+        let today = Local::now().date_naive();
+        let (mut app, dir) = open_app("saved-search", vec![Entry::new("Budget review".to_string(), BulletType::Task, today)]);
+        let search = SavedSearch::new("Money".to_string(), "budget".to_string());
+        app.journal.saved_searches.insert(search.id, search.clone());
+        app.current_tab = AppTab::Collections;
+        let found = |app: &App| app.saved_search_results(&search).map(|found| found.len()).map_err(str::to_owned);
+
+        assert_eq!(found(&app), Ok(0));
+        press(&mut app, KeyCode::Down);
+        assert_eq!(found(&app), Ok(1));
+
+        app.journal.add_entry(Entry::new("Budget approved".to_string(), BulletType::Note, today));
+        assert_eq!(found(&app), Ok(1));
+        press(&mut app, KeyCode::Down);
+        assert_eq!(found(&app), Ok(2));
+
+        let mut broken = search.clone();
+        broken.query = "type:idea".to_string();
+        app.journal.saved_searches.insert(search.id, broken);
+        press(&mut app, KeyCode::Down);
+        assert_eq!(found(&app), Err("Unknown type 'idea'; use task, event or note".to_string()));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn undo_history_survives_reloading_changes_from_another_process() {
+        let today = Local::now().date_naive();
+        let (mut app, dir) = open_app("reload", Vec::new());
+        app.create_entry(BulletType::Task, "Water the plants".to_string()).unwrap();
+        app.save().unwrap();
+
+        let other = Storage::new(&app.location).unwrap();
+        let mut journal = other.load_journal().unwrap();
+        journal.add_entry(Entry::new("Added elsewhere".to_string(), BulletType::Note, today));
+        other.save_journal(&journal).unwrap();
+
+        app.check_external_changes();
+        assert_eq!(app.journal.entries().len(), 2);
+        press(&mut app, KeyCode::Char('u'));
+        let left: Vec<&str> = app.journal.entries().iter().map(|entry| entry.content.as_str()).collect();
+        assert_eq!(left, ["Added elsewhere"]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn make_unsaved_edit(app: &mut App, content: &str) {
+        app.journal.add_entry(Entry::new(content.to_string(), BulletType::Note, Local::now().date_naive()));
+        app.mark_dirty();
+    }
+
+    fn saved_contents(app: &App) -> Vec<String> {
+        app.storage.load_journal().unwrap().entries().iter().map(|entry| entry.content.clone()).collect()
+    }
+
+    #[test]
+    fn autosave_waits_for_edits_to_settle() {
+        let (mut app, dir) = open_app("autosave", Vec::new());
+        make_unsaved_edit(&mut app, "Buy milk");
+        app.autosave();
+        assert!(app.dirty && saved_contents(&app).is_empty());
+
+        let delay = Duration::from_secs(app.config.get_config().journal.autosave_secs);
+        app.last_change = Instant::now().checked_sub(delay);
+        app.autosave();
+        assert!(!app.dirty);
+        assert_eq!(saved_contents(&app), ["Buy milk"]);
+
+        app.config.update_config(|config| config.journal.autosave_secs = 0).unwrap();
+        make_unsaved_edit(&mut app, "Call mum");
+        app.last_change = Instant::now().checked_sub(delay);
+        app.autosave();
+        assert!(app.dirty);
+        assert_eq!(saved_contents(&app), ["Buy milk"]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn quitting_with_unsaved_edits_asks_to_save_them() {
+        let (mut app, dir) = open_app("quit-save", Vec::new());
+        app.request_quit();
+        assert!(app.should_quit && !app.confirm_quit);
+
+        app.should_quit = false;
+        make_unsaved_edit(&mut app, "Buy milk");
+        app.request_quit();
+        assert!(app.confirm_quit && !app.should_quit);
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.confirm_quit && !app.should_quit && app.dirty);
+
+        app.request_quit();
+        press(&mut app, KeyCode::Char('y'));
+        assert!(app.should_quit && !app.dirty);
+        assert_eq!(saved_contents(&app), ["Buy milk"]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn declining_to_save_quits_without_writing() {
+        let (mut app, dir) = open_app("quit-discard", Vec::new());
+        make_unsaved_edit(&mut app, "Buy milk");
+        app.request_quit();
+        press(&mut app, KeyCode::Char('n'));
+        assert!(app.should_quit && app.dirty);
+        assert!(saved_contents(&app).is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+        press(app, KeyCode::Enter);
+    }
+
+    #[test]
+    fn collections_are_created_renamed_and_deleted_from_the_list() {
+        let (mut app, dir) = open_app("collections", Vec::new());
+        press(&mut app, KeyCode::Char('4'));
+        press(&mut app, KeyCode::Char('a'));
+        type_text(&mut app, "Reading");
+        type_text(&mut app, " Books to read ");
+        let collection = app.selected_collection().unwrap();
+        assert_eq!((collection.name.as_str(), collection.description.as_deref()), ("Reading", Some("Books to read")));
+
+        press(&mut app, KeyCode::Char('a'));
+        type_text(&mut app, "Reading");
+        assert_eq!(app.journal.collections.len(), 1);
+        assert_eq!(app.messages.back().map(String::as_str), Some("Collection 'Reading' already exists"));
+
+        press(&mut app, KeyCode::Char('r'));
+        for _ in 0.."Reading".len() {
+            press(&mut app, KeyCode::Backspace);
+        }
+        type_text(&mut app, "Books");
+        assert_eq!(app.selected_collection().map(|c| c.name.as_str()), Some("Books"));
+
+        app.save().unwrap();
+        let saved = app.storage.load_journal().unwrap();
+        let names: Vec<&str> = saved.sorted_collections().iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Books"]);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)).unwrap();
+        assert!(app.journal.collections.is_empty() && app.selected_collection.is_none());
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(app.journal.sorted_collections()[0].description.as_deref(), Some("Books to read"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn entries_are_added_toggled_and_deleted_inside_a_collection() {
+        let (mut app, dir) = open_app("collection-entries", Vec::new());
+        press(&mut app, KeyCode::Char('4'));
+        press(&mut app, KeyCode::Char('t'));
+        type_text(&mut app, "Dune");
+        assert_eq!(app.messages.back().map(String::as_str), Some("Select a collection first"));
+
+        press(&mut app, KeyCode::Char('a'));
+        type_text(&mut app, "Reading");
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.collection_focus, PaneFocus::Entries);
+        press(&mut app, KeyCode::Char('t'));
+        type_text(&mut app, "Dune #scifi");
+        assert!(app.journal.entries().is_empty());
+        assert_eq!(app.get_current_entries().len(), 1);
+
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char(' '));
+        let entry = app.get_current_entries()[0];
+        assert_eq!(entry.status, Some(TaskStatus::Complete));
+        assert_eq!(entry.tags, ["scifi"]);
+
+        app.save().unwrap();
+        let saved = app.storage.load_journal().unwrap();
+        assert_eq!(saved.sorted_collections()[0].entries[0].status, Some(TaskStatus::Complete));
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)).unwrap();
+        assert!(app.get_current_entries().is_empty());
+        assert_eq!(app.journal.collections.len(), 1);
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(app.get_current_entries()[0].content, "Dune #scifi");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn status_keys_move_the_selected_task_and_undo_steps_back() {
+        let today = Local::now().date_naive();
+        let task = Entry::new("File taxes".to_string(), BulletType::Task, today);
+        let note = Entry::new("Idea".to_string(), BulletType::Note, today);
+        let (mut app, dir) = open_app("status-keys", vec![task, note]);
+        let status = |app: &App| app.get_current_entries()[0].status;
+
+        press(&mut app, KeyCode::Char('H'));
+        assert!(app.messages.is_empty());
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('H'));
+        assert_eq!(app.messages.back().map(String::as_str), Some("No status changes recorded"));
+
+        press(&mut app, KeyCode::Char('x'));
+        press(&mut app, KeyCode::Char('x'));
+        assert_eq!(status(&app), Some(TaskStatus::Complete));
+        press(&mut app, KeyCode::Char('<'));
+        assert_eq!(app.messages.back().map(String::as_str), Some("Task marked scheduled"));
+        press(&mut app, KeyCode::Char('H'));
+        let history = app.messages.back().unwrap();
+        assert!(history.starts_with("History: complete ") && history.contains(", scheduled "), "{}", history);
+
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(status(&app), Some(TaskStatus::Complete));
+        assert_eq!(app.get_current_entries()[0].status_history.len(), 1);
+
+        press(&mut app, KeyCode::Char('j'));
+        press(&mut app, KeyCode::Char('~'));
+        assert_eq!(app.messages.back().map(String::as_str), Some("Only tasks have a status"));
+        assert_eq!(app.get_current_entries()[1].status, None);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
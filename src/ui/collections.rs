@@ -1,14 +1,16 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
-use crate::ui::app::App;
+use crate::models::SavedSearch;
+use crate::ui::app::{App, PaneFocus};
+use crate::ui::components::{create_entry_list, create_highlighted_entry_list, visible_rows};
 
 pub fn render_collections_view(f: &mut Frame, app: &App, area: Rect) {
     let config = app.config.get_config();
     let colors = &config.theme.colors;
     let borders = config.layout.border_style.to_ratatui_border();
 
-    if app.journal.collections.is_empty() {
-        let empty_msg = Paragraph::new("No collections yet.\n\nCollections feature coming soon!")
+    if app.journal.collections.is_empty() && app.journal.saved_searches.is_empty() {
+        let empty_msg = Paragraph::new("No collections yet.\n\nPress 'a' to create a collection, or 'S' in Search to save a search")
             .style(Style::default().fg(colors.muted()))
             .block(Block::default().borders(borders).title("Collections"))
             .alignment(Alignment::Center);
@@ -16,24 +18,195 @@ pub fn render_collections_view(f: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    let collections: Vec<ListItem> = app.journal.collections
-        .values()
-        .map(|collection| {
-            let content = format!("{} ({} entries)", 
-                collection.name, 
-                collection.entries.len()
-            );
-            ListItem::new(content)
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(35),
+            Constraint::Percentage(65),
+        ])
+        .split(area);
+
+    render_collection_list(f, app, chunks[0]);
+    render_collection_detail(f, app, chunks[1]);
+}
+
+fn render_collection_list(f: &mut Frame, app: &App, area: Rect) {
+    let config = app.config.get_config();
+    let colors = &config.theme.colors;
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    let collections = app.journal.sorted_collections();
+    let searches = app.journal.sorted_saved_searches();
+    let collection_rows = collections.iter().map(|collection| {
+        let content = format!("{} ({} entries)",
+            collection.name,
+            collection.entries.len()
+        );
+        (content, Style::default().fg(colors.text()))
+    });
+    // Saved searches follow the collections.
+    let search_rows = searches.iter().map(|search| {
+        let content = match app.saved_search_results(search) {
+            Ok(found) => format!("⌕ {} ({} found)", search.name, found.len()),
+            Err(_) => format!("⌕ {} (invalid query)", search.name),
+        };
+        (content, Style::default().fg(colors.secondary()))
+    });
+    let items: Vec<ListItem> = collection_rows
+        .chain(search_rows)
+        .enumerate()
+        .map(|(i, (content, style))| {
+            if Some(i) == app.selected_collection {
+                ListItem::new(content).style(style.bg(colors.muted()))
+            } else {
+                ListItem::new(content).style(style)
+            }
         })
         .collect();
 
-    let list = List::new(collections)
+    let border_color = if app.collection_focus == PaneFocus::List {
+        colors.accent()
+    } else {
+        colors.muted()
+    };
+
+    let list = List::new(items)
         .block(
             Block::default()
                 .borders(borders)
-                .title(format!("Collections ({})", app.journal.collections.len()))
+                .border_style(Style::default().fg(border_color))
+                .title(format!("Collections ({}) & Saved Searches ({})", collections.len(), searches.len()))
         )
         .highlight_style(Style::default().bg(colors.muted()));
-    
+
     f.render_widget(list, area);
-}
\ No newline at end of file
+}
+
+fn render_collection_detail(f: &mut Frame, app: &App, area: Rect) {
+    let config = app.config.get_config();
+    let colors = &config.theme.colors;
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    if let Some(search) = app.selected_saved_search() {
+        render_saved_search_detail(f, app, search, area);
+        return;
+    }
+
+    let Some(collection) = app.selected_collection() else {
+        let help_msg = Paragraph::new("Select a collection with j/k and press Enter to open it\n\na:new r:rename i:description (query of a saved search) Ctrl+d:delete")
+            .style(Style::default().fg(colors.muted()))
+            .block(Block::default().borders(borders).title("Collection"))
+            .alignment(Alignment::Center);
+        f.render_widget(help_msg, area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let description = collection.description.as_deref().unwrap_or("No description (press 'i' to add one)");
+    let header = Paragraph::new(description)
+        .style(Style::default().fg(colors.secondary()))
+        .block(Block::default().borders(borders).title(collection.name.as_str()));
+    f.render_widget(header, chunks[0]);
+
+    let focused = app.collection_focus == PaneFocus::Entries;
+    let border_color = if focused { colors.accent() } else { colors.muted() };
+
+    if collection.entries.is_empty() {
+        let empty_msg = Paragraph::new("No entries in this collection.\n\nPress 't', 'e', or 'n' to add one")
+            .style(Style::default().fg(colors.muted()))
+            .block(
+                Block::default()
+                    .borders(borders)
+                    .border_style(Style::default().fg(border_color))
+                    .title("Entries")
+            )
+            .alignment(Alignment::Center);
+        f.render_widget(empty_msg, chunks[1]);
+        return;
+    }
+
+    let entries: Vec<_> = collection.entries.iter().collect();
+    let selected = if focused { app.selected_entry } else { None };
+    let list = create_entry_list(&entries, selected, app)
+        .block(
+            Block::default()
+                .borders(borders)
+                .border_style(Style::default().fg(border_color))
+                .title(format!("Entries ({})", entries.len()))
+        );
+
+    f.render_widget(list, chunks[1]);
+}
+
+/// A saved search's query and its current results, which change with the journal.
+fn render_saved_search_detail(f: &mut Frame, app: &App, search: &SavedSearch, area: Rect) {
+    let config = app.config.get_config();
+    let colors = &config.theme.colors;
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let focused = app.collection_focus == PaneFocus::Entries;
+    let border_color = if focused { colors.accent() } else { colors.muted() };
+    let entries_block = |title: String| {
+        Block::default()
+            .borders(borders)
+            .border_style(Style::default().fg(border_color))
+            .title(title)
+    };
+
+    let found = app.saved_search_results(search);
+    let header_block = Block::default().borders(borders).title(format!("⌕ {}", search.name));
+    let header_block = match &found {
+        Ok(_) => header_block,
+        Err(e) => header_block
+            .border_style(Style::default().fg(colors.error()))
+            .title(Span::styled(format!(" - {}", e), Style::default().fg(colors.error()))),
+    };
+    let header = Paragraph::new(search.query.as_str())
+        .style(Style::default().fg(colors.secondary()))
+        .block(header_block);
+    f.render_widget(header, chunks[0]);
+
+    let found = match found {
+        Ok(found) if !found.is_empty() => found,
+        Ok(_) => {
+            let empty_msg = Paragraph::new("Nothing matches this search right now.\n\nPress 'i' to change the query")
+                .style(Style::default().fg(colors.muted()))
+                .block(entries_block("Entries".to_string()))
+                .alignment(Alignment::Center);
+            f.render_widget(empty_msg, chunks[1]);
+            return;
+        }
+        Err(_) => {
+            let error_msg = Paragraph::new("Press 'i' to fix the query")
+                .style(Style::default().fg(colors.muted()))
+                .block(entries_block("Entries".to_string()))
+                .alignment(Alignment::Center);
+            f.render_widget(error_msg, chunks[1]);
+            return;
+        }
+    };
+
+    let (entries, highlights): (Vec<_>, Vec<_>) = found.into_iter().unzip();
+
+    let selected = if focused { app.selected_entry } else { None };
+    let (shown, selected) = visible_rows(entries.len(), selected, chunks[1]);
+    let list = create_highlighted_entry_list(&entries[shown.clone()], &highlights[shown], selected, app)
+        .block(entries_block(format!("Entries ({})", entries.len())));
+
+    f.render_widget(list, chunks[1]);
+}
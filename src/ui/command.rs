@@ -0,0 +1,314 @@
+use crate::models::normalize_tag;
+use anyhow::{bail, Result};
+use chrono::NaiveDate;
+
+const COMMAND_NAMES: &[&str] = &[
+    "w", "q", "q!", "wq", "goto", "migrate", "theme", "tag", "collection", "search", "journal", "help",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Write,
+    Quit,
+    ForceQuit,
+    WriteQuit,
+    Goto(NaiveDate),
+    Migrate(Option<NaiveDate>),
+    Theme(String),
+    TagAdd(String),
+    TagRemove(String),
+    CollectionNew(String),
+    SearchSave(String),
+    Journal(Option<String>),
+    Help,
+}
+
+pub fn parse_command(input: &str, today: NaiveDate) -> Result<Command> {
+    let input = input.trim();
+    let (name, args) = match input.split_once(char::is_whitespace) {
+        Some((name, args)) => (name, args.trim()),
+        None => (input, ""),
+    };
+
+    match name {
+        "" => bail!("No command given"),
+        "w" | "write" => no_args(name, args, Command::Write),
+        "q" | "quit" => no_args(name, args, Command::Quit),
+        "q!" | "quit!" => no_args(name, args, Command::ForceQuit),
+        "wq" | "x" => no_args(name, args, Command::WriteQuit),
+        "help" | "h" => no_args(name, args, Command::Help),
+        "goto" | "g" => match args {
+            "" => bail!("Usage: goto <YYYY-MM-DD|today>"),
+            "today" => Ok(Command::Goto(today)),
+            date => Ok(Command::Goto(parse_date(date)?)),
+        },
+        "migrate" => match args {
+            "" => Ok(Command::Migrate(None)),
+            date => Ok(Command::Migrate(Some(parse_date(date)?))),
+        },
+        "theme" => match args {
+            "" => bail!("Usage: theme <name>"),
+            theme => Ok(Command::Theme(theme.to_string())),
+        },
+        "tag" => {
+            let (action, tag) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+            let tag = normalize_tag(tag);
+            if tag.is_empty() || tag.contains(char::is_whitespace) {
+                bail!("Usage: tag <add|remove> <tag>");
+            }
+            match action {
+                "add" => Ok(Command::TagAdd(tag)),
+                "remove" | "rm" => Ok(Command::TagRemove(tag)),
+                _ => bail!("Usage: tag <add|remove> <tag>"),
+            }
+        }
+        "collection" => {
+            let (action, collection_name) =
+                args.split_once(char::is_whitespace).unwrap_or((args, ""));
+            let collection_name = collection_name.trim();
+            match action {
+                "new" if !collection_name.is_empty() => {
+                    Ok(Command::CollectionNew(collection_name.to_string()))
+                }
+                _ => bail!("Usage: collection new <name>"),
+            }
+        }
+        "search" => {
+            let (action, search_name) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+            let search_name = search_name.trim();
+            match action {
+                "save" if !search_name.is_empty() => Ok(Command::SearchSave(search_name.to_string())),
+                _ => bail!("Usage: search save <name>"),
+            }
+        }
+        "journal" => match args {
+            "" => Ok(Command::Journal(None)),
+            journal => Ok(Command::Journal(Some(journal.to_string()))),
+        },
+        other => bail!("Unknown command: {}", other),
+    }
+}
+
+fn no_args(name: &str, args: &str, command: Command) -> Result<Command> {
+    if !args.is_empty() {
+        bail!("{} takes no arguments", name);
+    }
+    Ok(command)
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => Ok(date),
+        Err(_) => bail!("Invalid date '{}', expected YYYY-MM-DD", value),
+    }
+}
+
+/// Candidates for the word being typed, given the words before it.
+fn completion_candidates(previous: &[&str], themes: &[&str], journals: &[&str]) -> Vec<String> {
+    let candidates: &[&str] = match previous {
+        [] => COMMAND_NAMES,
+        ["goto"] | ["g"] => &["today"],
+        ["theme"] => themes,
+        ["tag"] => &["add", "remove"],
+        ["collection"] => &["new"],
+        ["search"] => &["save"],
+        ["journal"] => journals,
+        _ => &[],
+    };
+    candidates.iter().map(|c| c.to_string()).collect()
+}
+
+#[derive(Debug, Default)]
+pub struct CommandLine {
+    pub buffer: String,
+    history: Vec<String>,
+    history_index: Option<usize>,
+    completion: Option<Completion>,
+}
+
+#[derive(Debug)]
+struct Completion {
+    prefix: String,
+    candidates: Vec<String>,
+    index: usize,
+}
+
+impl CommandLine {
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.history_index = None;
+        self.completion = None;
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.buffer.push(c);
+        self.completion = None;
+    }
+
+    pub fn pop(&mut self) {
+        self.buffer.pop();
+        self.completion = None;
+    }
+
+    /// Takes the current input, recording it in the history.
+    pub fn submit(&mut self) -> String {
+        let input = std::mem::take(&mut self.buffer);
+        let trimmed = input.trim();
+        if !trimmed.is_empty() && self.history.last().map(String::as_str) != Some(trimmed) {
+            self.history.push(trimmed.to_string());
+        }
+        self.history_index = None;
+        self.completion = None;
+        input
+    }
+
+    pub fn history_prev(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let index = match self.history_index {
+            Some(index) => index.saturating_sub(1),
+            None => self.history.len() - 1,
+        };
+        self.history_index = Some(index);
+        self.buffer = self.history[index].clone();
+        self.completion = None;
+    }
+
+    pub fn history_next(&mut self) {
+        if let Some(index) = self.history_index {
+            if index + 1 < self.history.len() {
+                self.history_index = Some(index + 1);
+                self.buffer = self.history[index + 1].clone();
+            } else {
+                self.history_index = None;
+                self.buffer.clear();
+            }
+            self.completion = None;
+        }
+    }
+
+    /// Completes the word under the cursor, cycling through candidates on repeated calls.
+    pub fn complete(&mut self, themes: &[&str], journals: &[&str]) {
+        if let Some(completion) = &mut self.completion {
+            completion.index = (completion.index + 1) % completion.candidates.len();
+            self.buffer = format!("{}{}", completion.prefix, completion.candidates[completion.index]);
+            return;
+        }
+
+        let (prefix, word) = match self.buffer.rfind(char::is_whitespace) {
+            Some(pos) => self.buffer.split_at(pos + 1),
+            None => ("", self.buffer.as_str()),
+        };
+        let previous: Vec<&str> = prefix.split_whitespace().collect();
+        let candidates: Vec<String> = completion_candidates(&previous, themes, journals)
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .collect();
+
+        if candidates.is_empty() {
+            return;
+        }
+
+        let prefix = prefix.to_string();
+        self.buffer = format!("{}{}", prefix, candidates[0]);
+        if candidates.len() > 1 {
+            self.completion = Some(Completion { prefix, candidates, index: 0 });
+        } else {
+            self.buffer.push(' ');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn parse(input: &str) -> Result<Command> {
+        parse_command(input, day(17))
+    }
+
+    fn error(input: &str) -> String {
+        parse(input).unwrap_err().to_string()
+    }
+
+    fn typed(buffer: &str) -> CommandLine {
+        CommandLine { buffer: buffer.to_string(), ..CommandLine::default() }
+    }
+
+    #[test]
+    fn commands_parse_with_their_arguments() {
+        assert_eq!(parse("  wq ").unwrap(), Command::WriteQuit);
+        assert_eq!(parse("quit!").unwrap(), Command::ForceQuit);
+        assert_eq!(parse("goto today").unwrap(), Command::Goto(day(17)));
+        assert_eq!(parse("g 2026-10-03").unwrap(), Command::Goto(day(3)));
+        assert_eq!(parse("migrate").unwrap(), Command::Migrate(None));
+        assert_eq!(parse("migrate 2026-10-16").unwrap(), Command::Migrate(Some(day(16))));
+        assert_eq!(parse("theme  nord").unwrap(), Command::Theme("nord".to_string()));
+        assert_eq!(parse("tag add #Work").unwrap(), Command::TagAdd("work".to_string()));
+        assert_eq!(parse("tag rm @phone").unwrap(), Command::TagRemove("@phone".to_string()));
+        assert_eq!(parse("collection new Reading list").unwrap(), Command::CollectionNew("Reading list".to_string()));
+        assert_eq!(parse("search save stale work").unwrap(), Command::SearchSave("stale work".to_string()));
+        assert_eq!(parse("journal").unwrap(), Command::Journal(None));
+        assert_eq!(parse("journal work").unwrap(), Command::Journal(Some("work".to_string())));
+    }
+
+    #[test]
+    fn bad_commands_explain_themselves() {
+        assert_eq!(error(""), "No command given");
+        assert_eq!(error("frobnicate now"), "Unknown command: frobnicate");
+        assert_eq!(error("w journal.json"), "w takes no arguments");
+        assert_eq!(error("goto"), "Usage: goto <YYYY-MM-DD|today>");
+        assert_eq!(error("goto tomorrow"), "Invalid date 'tomorrow', expected YYYY-MM-DD");
+        assert_eq!(error("tag add two words"), "Usage: tag <add|remove> <tag>");
+        assert_eq!(error("collection new"), "Usage: collection new <name>");
+        assert_eq!(error("search load x"), "Usage: search save <name>");
+    }
+
+    #[test]
+    fn completion_extends_the_word_being_typed() {
+        let mut line = typed("mi");
+        line.complete(&[], &[]);
+        assert_eq!(line.buffer, "migrate ");
+
+        let mut line = typed("theme d");
+        line.complete(&["dark", "default", "nord"], &[]);
+        assert_eq!(line.buffer, "theme dark");
+        line.complete(&["dark", "default", "nord"], &[]);
+        assert_eq!(line.buffer, "theme default");
+        line.complete(&["dark", "default", "nord"], &[]);
+        assert_eq!(line.buffer, "theme dark");
+
+        let mut line = typed("journal ");
+        line.complete(&[], &["work"]);
+        assert_eq!(line.buffer, "journal work ");
+
+        let mut line = typed("tag add x");
+        line.complete(&[], &[]);
+        assert_eq!(line.buffer, "tag add x");
+    }
+
+    #[test]
+    fn history_recalls_distinct_submissions() {
+        let mut line = typed("w");
+        line.submit();
+        line.buffer = "w".to_string();
+        line.submit();
+        line.buffer = "goto today".to_string();
+        line.submit();
+
+        line.history_prev();
+        assert_eq!(line.buffer, "goto today");
+        line.history_prev();
+        assert_eq!(line.buffer, "w");
+        line.history_prev();
+        assert_eq!(line.buffer, "w");
+        line.history_next();
+        line.history_next();
+        assert_eq!(line.buffer, "");
+    }
+}
@@ -1,6 +1,9 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 use crate::ui::app::{App, AppTab, AppMode};
+use crate::models::inline_tag_ranges;
+use chrono::Datelike;
+use std::ops::Range;
 
 pub fn render_tabs(f: &mut Frame, app: &App, area: Rect) {
     let tab_titles = vec![
@@ -9,6 +12,7 @@ pub fn render_tabs(f: &mut Frame, app: &App, area: Rect) {
         "Future (3)",
         "Collections (4)",
         "Search (5)",
+        "Tags (6)",
     ];
     
     let selected_tab = match app.current_tab {
@@ -17,6 +21,7 @@ pub fn render_tabs(f: &mut Frame, app: &App, area: Rect) {
         AppTab::Future => 2,
         AppTab::Collections => 3,
         AppTab::Search => 4,
+        AppTab::Tags => 5,
     };
 
     let config = app.config.get_config();
@@ -24,7 +29,7 @@ pub fn render_tabs(f: &mut Frame, app: &App, area: Rect) {
     let borders = config.layout.border_style.to_ratatui_border();
 
     let tabs = Tabs::new(tab_titles)
-        .block(Block::default().borders(borders).title("Bullet Journal"))
+        .block(Block::default().borders(borders).title(format!("Bullet Journal - {}", app.location.label())))
         .select(selected_tab)
         .style(Style::default().fg(colors.primary()))
         .highlight_style(
@@ -46,6 +51,14 @@ pub fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
                     crate::ui::app::InputMode::Task => "INSERT [TASK]",
                     crate::ui::app::InputMode::Event => "INSERT [EVENT]", 
                     crate::ui::app::InputMode::Note => "INSERT [NOTE]",
+                    crate::ui::app::InputMode::CollectionName => "INSERT [NEW COLLECTION]",
+                    crate::ui::app::InputMode::CollectionRename => "INSERT [RENAME COLLECTION]",
+                    crate::ui::app::InputMode::CollectionDescription => "INSERT [DESCRIPTION]",
+                    crate::ui::app::InputMode::SavedSearchName => "INSERT [SAVE SEARCH AS]",
+                    crate::ui::app::InputMode::SavedSearchRename => "INSERT [RENAME SAVED SEARCH]",
+                    crate::ui::app::InputMode::SavedSearchQuery => "INSERT [SAVED QUERY]",
+                    crate::ui::app::InputMode::EditEntry(_) => "INSERT [EDIT]",
+                    crate::ui::app::InputMode::EditDate(_) => "INSERT [MOVE TO DATE]",
                 }
             } else if app.current_tab == AppTab::Search {
                 "INSERT [SEARCH]"
@@ -56,7 +69,9 @@ pub fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
         AppMode::Command => "COMMAND",
     };
 
-    let input_text = if !app.input_buffer.is_empty() {
+    let input_text = if app.mode == AppMode::Command {
+        format!(" | :{}", app.command_line.buffer)
+    } else if app.input_mode.is_some() || !app.input_buffer.is_empty() {
         format!(" | {}", app.input_buffer)
     } else {
         String::new()
@@ -69,12 +84,22 @@ pub fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
     };
 
     let hotkeys = if app.mode == AppMode::Normal {
-        " | t:task e:event n:note ?:help q:quit"
+        " | t:task e:event n:note ::command ?:help q:quit"
+    } else if app.mode == AppMode::Command {
+        " | Tab:complete Up/Down:history Esc:cancel Enter:run"
     } else {
         " | Esc:cancel Enter:confirm"
     };
 
-    let status_text = format!("{}{}{}{}", mode_text, input_text, search_text, hotkeys);
+    let modified = if app.dirty { " [+]" } else { "" };
+
+    let status_text = if app.external_change {
+        "Journal changed on disk! m:merge r:reload (discard mine) o:overwrite with mine".to_string()
+    } else if app.confirm_quit {
+        "Unsaved changes! y:save and quit  n:quit without saving  any other key:cancel".to_string()
+    } else {
+        format!("{}{}{}{}{}", mode_text, modified, input_text, search_text, hotkeys)
+    };
     
     let config = app.config.get_config();
     let colors = &config.theme.colors;
@@ -85,6 +110,42 @@ pub fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
         .block(Block::default().borders(borders));
     
     f.render_widget(paragraph, area);
+
+    if app.mode == AppMode::Insert && app.input_mode.is_some() {
+        let before_cursor: String = app.input_buffer.chars().take(app.input_cursor).collect();
+        let offset = Line::from(format!("{}{} | {}", mode_text, modified, before_cursor)).width() as u16;
+        let x = (area.x + 1 + offset).min(area.right().saturating_sub(2));
+        f.set_cursor_position((x, area.y + 1));
+    }
+}
+
+pub fn render_journal_picker(f: &mut Frame, app: &App, picked: usize, area: Rect) {
+    let config = app.config.get_config();
+    let colors = &config.theme.colors;
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    let items: Vec<ListItem> = config.journal_names()
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let label = if Some(name) == app.location.name.as_deref() {
+                format!("{} (open)", name)
+            } else {
+                name.to_string()
+            };
+            let style = Style::default().fg(colors.text());
+            if i == picked {
+                ListItem::new(label).style(style.bg(colors.muted()))
+            } else {
+                ListItem::new(label).style(style)
+            }
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(borders).title("Switch journal (Enter:open Esc:cancel)"));
+
+    f.render_widget(list, area);
 }
 
 pub fn render_messages(f: &mut Frame, app: &App, area: Rect) {
@@ -92,8 +153,11 @@ pub fn render_messages(f: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
+    // Show the most recent messages that fit inside the borders.
+    let visible = area.height.saturating_sub(2) as usize;
     let messages: Vec<Line> = app.messages
         .iter()
+        .skip(app.messages.len().saturating_sub(visible))
         .map(|msg| Line::from(msg.clone()))
         .collect();
 
@@ -116,7 +180,7 @@ pub fn render_help(f: &mut Frame, app: &App, area: Rect) {
         Line::from(""),
         Line::from("Navigation:"),
         Line::from("  Tab/Shift+Tab - Switch tabs"),
-        Line::from("  1-5 - Jump to specific tab"),
+        Line::from("  1-6 - Jump to specific tab"),
         Line::from("  h/j/k/l or arrows - Navigate"),
         Line::from(""),
         Line::from("Entry Creation:"),
@@ -126,10 +190,52 @@ pub fn render_help(f: &mut Frame, app: &App, area: Rect) {
         Line::from(""),
         Line::from("Entry Management:"),
         Line::from("  Space/Enter - Toggle task completion"),
+        Line::from("  x / o - Mark task complete / incomplete"),
+        Line::from("  > / < / ~ - Mark task migrated / scheduled / irrelevant"),
+        Line::from("  H - Show status history of selected task"),
         Line::from("  Ctrl+d - Delete selected entry"),
+        Line::from("  i - Edit selected entry"),
+        Line::from("  T - Cycle entry type (task/event/note)"),
+        Line::from("  D - Move entry to a date or month (YYYY-MM), [ / ] - Move one day earlier/later"),
+        Line::from("  + / - - Raise / lower priority, ! - Toggle inspiration"),
+        Line::from("  * / ! before new entry text - Priority / inspiration signifier"),
+        Line::from("  P - Sort by priority, F - Filter by priority"),
+        Line::from("  m - Review incomplete tasks from previous days"),
+        Line::from("  u / Ctrl+r - Undo / redo"),
+        Line::from(""),
+        Line::from("Collections (tab 4):"),
+        Line::from("  a - New collection"),
+        Line::from("  r / i - Rename / describe selected collection"),
+        Line::from("  Ctrl+d - Delete selected collection"),
+        Line::from("  Enter/l - Open collection, h - Back to list"),
+        Line::from("  t/e/n, Space, Ctrl+d - Manage entries of open collection"),
+        Line::from("  Saved searches (⌕) list what their query finds now; r / i - Rename / edit query"),
+        Line::from(""),
+        Line::from("Future Log (tab 3):"),
+        Line::from("  h/l - Select a month, t/e/n - Plan an entry for the whole month"),
+        Line::from("  v - Show 6 or 12 months"),
+        Line::from(""),
+        Line::from("Tags (tab 6):"),
+        Line::from("  #tag / @context in an entry tags it"),
+        Line::from("  Enter/l - Show entries with the selected tag, h - Back to list"),
+        Line::from(""),
+        Line::from("Search (tab 5):"),
+        Line::from("  Enter - Open the selected result in its daily, monthly or future log, Backspace - Back to the results"),
+        Line::from("  S - Save the query, listed under Collections"),
+        Line::from(""),
+        Line::from("Commands (press : to enter):"),
+        Line::from("  :w / :q / :wq / :q! - Save / quit / save and quit / quit without saving"),
+        Line::from("  :goto <YYYY-MM-DD|today> - Jump to a day"),
+        Line::from("  :migrate [YYYY-MM-DD] - Migrate incomplete tasks to the current day"),
+        Line::from("  :theme <name> - Switch color theme"),
+        Line::from("  :tag add|remove <tag> - Tag the selected entry"),
+        Line::from("  :collection new <name> - Create a collection"),
+        Line::from("  :search save <name> - Save the current search"),
+        Line::from("  :journal [name] - Switch journal"),
         Line::from(""),
         Line::from("Other:"),
-        Line::from("  / - Search"),
+        Line::from("  / - Search, e.g. type:task tag:work date:2026-09..2026-10 priority:>2 \"phrase\" -word"),
+        Line::from("  J - Switch journal"),
         Line::from("  Ctrl+s - Save"),
         Line::from("  ? - Toggle this help"),
         Line::from("  q - Quit"),
@@ -157,6 +263,17 @@ pub fn render_help(f: &mut Frame, app: &App, area: Rect) {
 }
 
 pub fn create_entry_list<'a>(entries: &'a [&'a crate::models::Entry], selected: Option<usize>, app: &App) -> List<'a> {
+    create_highlighted_entry_list(entries, &[], selected, app)
+}
+
+/// An entry list with the given content bytes of each entry, such as search
+/// matches, highlighted. Entries past the end of `highlights` get none.
+pub fn create_highlighted_entry_list<'a>(
+    entries: &'a [&'a crate::models::Entry],
+    highlights: &[&[Range<usize>]],
+    selected: Option<usize>,
+    app: &App,
+) -> List<'a> {
     let config = app.config.get_config();
     let colors = &config.theme.colors;
     let borders = config.layout.border_style.to_ratatui_border();
@@ -165,33 +282,126 @@ pub fn create_entry_list<'a>(entries: &'a [&'a crate::models::Entry], selected:
         .iter()
         .enumerate()
         .map(|(i, entry)| {
-            let symbol = entry.symbol();
-            let content = format!("{} {}", symbol, entry.content);
-            
-            let style = if entry.bullet_type == crate::models::BulletType::Task {
-                match entry.status {
-                    Some(crate::models::TaskStatus::Complete) => Style::default().fg(colors.success()),
-                    Some(crate::models::TaskStatus::Migrated) => Style::default().fg(colors.warning()),
-                    Some(crate::models::TaskStatus::Irrelevant) => Style::default().fg(colors.muted()),
-                    _ => Style::default().fg(colors.text()),
-                }
-            } else if entry.bullet_type == crate::models::BulletType::Event {
-                Style::default().fg(colors.secondary())
-            } else {
-                Style::default().fg(colors.primary())
-            };
+            let highlights = highlights.get(i).copied().unwrap_or_default();
+            entry_item(entry, Vec::new(), highlights, Some(i) == selected, colors)
+        })
+        .collect();
 
-            let mut item = ListItem::new(content).style(style);
-            
-            if Some(i) == selected {
-                item = item.style(style.bg(colors.muted()));
-            }
-            
-            item
+    List::new(items)
+        .block(Block::default().borders(borders))
+        .highlight_style(Style::default().bg(colors.muted()))
+}
+
+/// An entry list for one month, each entry led by its day of the month; those
+/// planned for the whole month have none.
+pub fn create_month_entry_list<'a>(
+    entries: &'a [&'a crate::models::Entry],
+    selected: Option<usize>,
+    app: &App,
+) -> List<'a> {
+    let config = app.config.get_config();
+    let colors = &config.theme.colors;
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    let items: Vec<ListItem> = entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let day = if entry.whole_month { "   ".to_string() } else { format!("{:>2} ", entry.date.day()) };
+            let label = vec![Span::styled(day, Style::default().fg(colors.muted()))];
+            entry_item(entry, label, &[], Some(i) == selected, colors)
         })
         .collect();
 
     List::new(items)
         .block(Block::default().borders(borders))
         .highlight_style(Style::default().bg(colors.muted()))
-}
\ No newline at end of file
+}
+
+/// One row of an entry list, after the given leading spans.
+fn entry_item<'a>(
+    entry: &'a crate::models::Entry,
+    mut spans: Vec<Span<'a>>,
+    highlights: &[Range<usize>],
+    selected: bool,
+    colors: &crate::config::ColorScheme,
+) -> ListItem<'a> {
+    spans.extend(signifier_column(entry, colors));
+    spans.push(Span::raw(format!("{} ", entry.symbol())));
+    spans.extend(content_spans(&entry.content, highlights, colors));
+    let content = Line::from(spans);
+
+    let style = if entry.bullet_type == crate::models::BulletType::Task {
+        match entry.status {
+            Some(crate::models::TaskStatus::Complete) => Style::default().fg(colors.success()),
+            Some(crate::models::TaskStatus::Migrated) => Style::default().fg(colors.warning()),
+            Some(crate::models::TaskStatus::Irrelevant) => Style::default().fg(colors.muted()),
+            _ => Style::default().fg(colors.text()),
+        }
+    } else if entry.bullet_type == crate::models::BulletType::Event {
+        Style::default().fg(colors.secondary())
+    } else {
+        Style::default().fg(colors.primary())
+    };
+
+    if selected {
+        ListItem::new(content).style(style.bg(colors.muted()))
+    } else {
+        ListItem::new(content).style(style)
+    }
+}
+
+/// The rows of a list that fit in a bordered `area`, scrolled to keep the
+/// selection in view, and the selection within them. Long lists such as search
+/// results only build these rows.
+pub fn visible_rows(len: usize, selected: Option<usize>, area: Rect) -> (Range<usize>, Option<usize>) {
+    let rows = area.height.saturating_sub(2).max(1) as usize;
+    let offset = selected.map_or(0, |selected| (selected + 1).saturating_sub(rows));
+    (offset..len.min(offset + rows), selected.map(|selected| selected - offset))
+}
+
+/// Entry content with inline tags in the accent colour and highlighted bytes
+/// underlined in bold.
+fn content_spans<'a>(content: &'a str, highlights: &[Range<usize>], colors: &crate::config::ColorScheme) -> Vec<Span<'a>> {
+    let tags = inline_tag_ranges(content);
+    let mut cuts: Vec<usize> = tags
+        .iter()
+        .chain(highlights)
+        .flat_map(|range| [range.start, range.end])
+        .chain([0, content.len()])
+        .collect();
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    let covers = |ranges: &[Range<usize>], piece: &Range<usize>| {
+        ranges.iter().any(|range| range.start <= piece.start && piece.end <= range.end)
+    };
+    cuts.windows(2)
+        .map(|cut| {
+            let piece = cut[0]..cut[1];
+            let mut style = Style::default();
+            if covers(&tags, &piece) {
+                style = style.fg(colors.accent());
+            }
+            if covers(highlights, &piece) {
+                style = style.fg(colors.warning()).add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+            }
+            Span::styled(&content[piece], style)
+        })
+        .collect()
+}
+
+/// The signifier column left of the bullet: `*` with the priority level when
+/// above 1, and `!` for inspiration, padded so bullets line up.
+fn signifier_column(entry: &crate::models::Entry, colors: &crate::config::ColorScheme) -> Vec<Span<'static>> {
+    let priority = match entry.priority {
+        Some(1) => "* ".to_string(),
+        Some(priority) => format!("*{}", priority),
+        None => "  ".to_string(),
+    };
+    let inspiration = if entry.inspiration { "! " } else { "  " };
+    vec![
+        Span::styled(priority, Style::default().fg(colors.warning()).add_modifier(Modifier::BOLD)),
+        Span::styled(inspiration, Style::default().fg(colors.accent()).add_modifier(Modifier::BOLD)),
+    ]
+}
@@ -28,22 +28,28 @@ fn render_daily_header(f: &mut Frame, app: &App, area: Rect) {
     let colors = &config.theme.colors;
     let borders = config.layout.border_style.to_ratatui_border();
     
+    let title = app.log_title("Daily Log");
+
     let paragraph = Paragraph::new(date_str)
         .style(Style::default().fg(colors.accent()).add_modifier(Modifier::BOLD))
-        .block(Block::default().borders(borders).title("Daily Log"))
+        .block(Block::default().borders(borders).title(title))
         .alignment(Alignment::Center);
     
     f.render_widget(paragraph, area);
 }
 
 fn render_daily_entries(f: &mut Frame, app: &App, area: Rect) {
-    let entries = app.journal.entries_for_date(app.current_date);
+    let entries = app.get_current_entries();
     let config = app.config.get_config();
     let colors = &config.theme.colors;
     let borders = config.layout.border_style.to_ratatui_border();
     
     if entries.is_empty() {
-        let empty_msg = Paragraph::new("No entries for today.\n\nPress 't' for task, 'e' for event, 'n' for note")
+        let text = match app.min_priority {
+            Some(min) => format!("No entries with priority {} or higher.\n\nPress 'F' to change the filter", min),
+            None => "No entries for today.\n\nPress 't' for task, 'e' for event, 'n' for note".to_string(),
+        };
+        let empty_msg = Paragraph::new(text)
             .style(Style::default().fg(colors.muted()))
             .block(Block::default().borders(borders).title("Entries"))
             .alignment(Alignment::Center);
@@ -55,7 +61,7 @@ fn render_daily_entries(f: &mut Frame, app: &App, area: Rect) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("Entries ({})", entries.len()))
+                .title(format!("Entries ({}){}", entries.len(), app.arrangement_label()))
         );
     
     f.render_widget(list, area);
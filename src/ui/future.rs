@@ -1,34 +1,65 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 use crate::ui::app::App;
-use crate::ui::components::create_entry_list;
-use chrono::Local;
+use crate::ui::components::create_month_entry_list;
+use chrono::NaiveDate;
 
 pub fn render_future_view(f: &mut Frame, app: &App, area: Rect) {
-    let today = Local::now().date_naive();
-    let future_entries: Vec<_> = app.journal.entries.iter()
-        .filter(|entry| entry.date > today)
-        .collect();
+    let config = app.config.get_config();
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    let block = Block::default()
+        .borders(borders)
+        .title(app.log_title(&format!("Future Log ({} months){}", app.future_months_shown, app.arrangement_label())));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let months = app.future_grid();
+    let columns = if months.len() > 6 { 4 } else { 3 };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, months.len().div_ceil(columns) as u32); months.len().div_ceil(columns)])
+        .split(inner);
 
+    for (row, months) in rows.iter().zip(months.chunks(columns)) {
+        let cells = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+            .split(*row);
+        for (cell, month) in cells.iter().zip(months) {
+            render_month(f, app, *month, *cell);
+        }
+    }
+}
+
+fn render_month(f: &mut Frame, app: &App, month: (i32, u32), area: Rect) {
     let config = app.config.get_config();
     let colors = &config.theme.colors;
     let borders = config.layout.border_style.to_ratatui_border();
 
-    if future_entries.is_empty() {
-        let empty_msg = Paragraph::new("No future entries.\n\nPress 't', 'e', or 'n' to add entries for future dates")
+    let entries = app.month_log(month);
+    let is_selected = month == app.future_month;
+    let name = NaiveDate::from_ymd_opt(month.0, month.1, 1).map_or_else(String::new, |date| date.format("%B %Y").to_string());
+    let block = Block::default()
+        .borders(borders)
+        .border_style(if is_selected { Style::default().fg(colors.accent()) } else { Style::default() })
+        .title(format!("{} ({})", name, entries.len()));
+
+    if entries.is_empty() {
+        let text = match (is_selected, app.min_priority) {
+            (_, Some(min)) => format!("Nothing at priority {}+", min),
+            (true, None) => "Press 't', 'e', or 'n' to plan".to_string(),
+            (false, None) => String::new(),
+        };
+        let empty_msg = Paragraph::new(text)
             .style(Style::default().fg(colors.muted()))
-            .block(Block::default().borders(borders).title("Future Log"))
+            .block(block)
             .alignment(Alignment::Center);
         f.render_widget(empty_msg, area);
         return;
     }
 
-    let list = create_entry_list(&future_entries, app.selected_entry, app)
-        .block(
-            Block::default()
-                .borders(borders)
-                .title(format!("Future Log ({} entries)", future_entries.len()))
-        );
-    
+    let selected = if is_selected { app.selected_entry } else { None };
+    let list = create_month_entry_list(&entries, selected, app).block(block);
     f.render_widget(list, area);
-}
\ No newline at end of file
+}
@@ -0,0 +1,93 @@
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use crate::ui::app::{App, MigrationReview};
+
+pub fn render_migration_view(f: &mut Frame, app: &App, review: &MigrationReview, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(if review.collection_picker.is_some() { 8 } else { 3 }),
+        ])
+        .split(area);
+
+    render_task_list(f, app, review, chunks[0]);
+
+    if let Some(picked) = review.collection_picker {
+        render_collection_picker(f, app, picked, chunks[1]);
+    } else {
+        render_migration_prompt(f, app, review, chunks[1]);
+    }
+}
+
+fn render_task_list(f: &mut Frame, app: &App, review: &MigrationReview, area: Rect) {
+    let config = app.config.get_config();
+    let colors = &config.theme.colors;
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    let items: Vec<ListItem> = review.tasks
+        .iter()
+        .filter_map(|id| app.journal.get_entry(*id))
+        .enumerate()
+        .map(|(i, entry)| {
+            let content = format!("{}  {} {}", entry.date.format("%Y-%m-%d"), entry.symbol(), entry.content);
+            let style = Style::default().fg(colors.text());
+            if i == review.selected {
+                ListItem::new(content).style(style.bg(colors.muted()))
+            } else {
+                ListItem::new(content).style(style)
+            }
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(borders)
+                .border_style(Style::default().fg(colors.accent()))
+                .title(format!("Migration Review ({} incomplete tasks from previous days)", review.tasks.len()))
+        );
+
+    f.render_widget(list, area);
+}
+
+fn render_collection_picker(f: &mut Frame, app: &App, picked: usize, area: Rect) {
+    let config = app.config.get_config();
+    let colors = &config.theme.colors;
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    let items: Vec<ListItem> = app.journal.sorted_collections()
+        .iter()
+        .enumerate()
+        .map(|(i, collection)| {
+            let style = Style::default().fg(colors.text());
+            if i == picked {
+                ListItem::new(collection.name.clone()).style(style.bg(colors.muted()))
+            } else {
+                ListItem::new(collection.name.clone()).style(style)
+            }
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(borders).title("Move to collection (Enter:confirm Esc:cancel)"));
+
+    f.render_widget(list, area);
+}
+
+fn render_migration_prompt(f: &mut Frame, app: &App, review: &MigrationReview, area: Rect) {
+    let config = app.config.get_config();
+    let colors = &config.theme.colors;
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    let text = match &review.schedule_input {
+        Some(input) => format!("Schedule for (YYYY-MM-DD, or YYYY-MM for the month): {} | Enter:confirm Esc:cancel", input),
+        None => ">:migrate to today  <:schedule  c:move to collection  x:strike  A:migrate all  Esc:close".to_string(),
+    };
+
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(colors.accent()))
+        .block(Block::default().borders(borders));
+
+    f.render_widget(paragraph, area);
+}
@@ -1,7 +1,10 @@
 pub mod app;
+pub mod command;
 pub mod components;
 pub mod daily;
 pub mod monthly;
 pub mod future;
 pub mod collections;
-pub mod search;
\ No newline at end of file
+pub mod migration;
+pub mod search;
+pub mod tags;
\ No newline at end of file
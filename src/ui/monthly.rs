@@ -1,7 +1,7 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 use crate::ui::app::App;
-use crate::ui::components::create_entry_list;
+use crate::ui::components::create_month_entry_list;
 use chrono::{NaiveDate, Datelike};
 
 pub fn render_monthly_view(f: &mut Frame, app: &App, area: Rect) {
@@ -86,26 +86,29 @@ fn render_calendar(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_monthly_entries(f: &mut Frame, app: &App, area: Rect) {
-    let (year, month) = app.selected_month;
-    let entries = app.journal.entries_for_month(year, month);
+    let entries = app.get_current_entries();
     let config = app.config.get_config();
     let colors = &config.theme.colors;
     let borders = config.layout.border_style.to_ratatui_border();
     
     if entries.is_empty() {
-        let empty_msg = Paragraph::new("No entries this month")
+        let text = match app.min_priority {
+            Some(min) => format!("No entries with priority {} or higher this month", min),
+            None => "No entries this month".to_string(),
+        };
+        let empty_msg = Paragraph::new(text)
             .style(Style::default().fg(colors.muted()))
-            .block(Block::default().borders(borders).title("Monthly Entries"))
+            .block(Block::default().borders(borders).title(app.log_title("Monthly Entries")))
             .alignment(Alignment::Center);
         f.render_widget(empty_msg, area);
         return;
     }
 
-    let list = create_entry_list(&entries, app.selected_entry, app)
+    let list = create_month_entry_list(&entries, app.selected_entry, app)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("Monthly Entries ({})", entries.len()))
+                .title(app.log_title(&format!("Monthly Entries ({}){}", entries.len(), app.arrangement_label())))
         );
     
     f.render_widget(list, area);
@@ -1,7 +1,13 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 use crate::ui::app::App;
-use crate::ui::components::create_entry_list;
+use crate::ui::components::{create_highlighted_entry_list, visible_rows};
+
+const SEARCH_HELP: &str = "Press / to start searching\n\n\
+    Words match entry content and tags, forgiving typos; \"exact phrases\" match as written\n\
+    Best matches and recent entries come first\n\
+    Filters: type:task  status:incomplete  tag:work  date:2026-09..2026-10  priority:>2\n\
+    Prefix any term with - to exclude it";
 
 pub fn render_search_view(f: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
@@ -27,9 +33,17 @@ fn render_search_input(f: &mut Frame, app: &App, area: Rect) {
     let colors = &config.theme.colors;
     let borders = config.layout.border_style.to_ratatui_border();
 
+    let block = match app.parsed_search() {
+        Some(Err(e)) => Block::default()
+            .borders(borders)
+            .border_style(Style::default().fg(colors.error()))
+            .title(Span::styled(format!("Search Query - {}", e), Style::default().fg(colors.error()))),
+        _ => Block::default().borders(borders).title("Search Query"),
+    };
+
     let paragraph = Paragraph::new(query_display)
         .style(Style::default().fg(colors.text()))
-        .block(Block::default().borders(borders).title("Search Query"));
+        .block(block);
     
     f.render_widget(paragraph, area);
 }
@@ -39,16 +53,27 @@ fn render_search_results(f: &mut Frame, app: &App, area: Rect) {
     let colors = &config.theme.colors;
     let borders = config.layout.border_style.to_ratatui_border();
     
-    if app.search_query.is_empty() {
-        let help_msg = Paragraph::new("Press / to start searching\n\nSearch will match entry content and tags")
-            .style(Style::default().fg(colors.muted()))
-            .block(Block::default().borders(borders).title("Search Results"))
-            .alignment(Alignment::Center);
-        f.render_widget(help_msg, area);
-        return;
+    match app.parsed_search() {
+        Some(Ok(_)) => {}
+        Some(Err(_)) => {
+            let error_msg = Paragraph::new("Fix the query above to see results")
+                .style(Style::default().fg(colors.muted()))
+                .block(Block::default().borders(borders).title("Search Results"))
+                .alignment(Alignment::Center);
+            f.render_widget(error_msg, area);
+            return;
+        }
+        None => {
+            let help_msg = Paragraph::new(SEARCH_HELP)
+                .style(Style::default().fg(colors.muted()))
+                .block(Block::default().borders(borders).title("Search Results"))
+                .alignment(Alignment::Center);
+            f.render_widget(help_msg, area);
+            return;
+        }
     }
 
-    let results = app.journal.search_entries(&app.search_query);
+    let (results, highlights): (Vec<_>, Vec<_>) = app.search_results().into_iter().unzip();
     
     if results.is_empty() {
         let no_results_msg = Paragraph::new(format!("No results found for '{}'", app.search_query))
@@ -59,11 +84,12 @@ fn render_search_results(f: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    let list = create_entry_list(&results, app.selected_entry, app)
+    let (shown, selected) = visible_rows(results.len(), app.selected_entry, area);
+    let list = create_highlighted_entry_list(&results[shown.clone()], &highlights[shown], selected, app)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("Search Results ({} found)", results.len()))
+                .title(format!("Search Results ({} found, Enter to open)", results.len()))
         );
     
     f.render_widget(list, area);
@@ -0,0 +1,107 @@
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use crate::ui::app::{App, PaneFocus};
+use crate::ui::components::create_entry_list;
+
+pub fn render_tags_view(f: &mut Frame, app: &App, area: Rect) {
+    let config = app.config.get_config();
+    let colors = &config.theme.colors;
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    if app.journal.tag_counts().is_empty() {
+        let empty_msg = Paragraph::new("No tags yet.\n\nWrite #tags or @contexts in an entry to tag it")
+            .style(Style::default().fg(colors.muted()))
+            .block(Block::default().borders(borders).title("Tags"))
+            .alignment(Alignment::Center);
+        f.render_widget(empty_msg, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(35),
+            Constraint::Percentage(65),
+        ])
+        .split(area);
+
+    render_tag_list(f, app, chunks[0]);
+    render_tagged_entries(f, app, chunks[1]);
+}
+
+/// How a stored tag is written in content: contexts keep their `@`, tags get a `#`.
+pub fn tag_label(tag: &str) -> String {
+    if tag.starts_with('@') {
+        tag.to_string()
+    } else {
+        format!("#{}", tag)
+    }
+}
+
+fn render_tag_list(f: &mut Frame, app: &App, area: Rect) {
+    let config = app.config.get_config();
+    let colors = &config.theme.colors;
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    let tags = app.journal.tag_counts();
+    let items: Vec<ListItem> = tags
+        .iter()
+        .enumerate()
+        .map(|(i, (tag, count))| {
+            let content = format!("{} ({})", tag_label(tag), count);
+            let style = Style::default().fg(colors.text());
+            if Some(i) == app.selected_tag {
+                ListItem::new(content).style(style.bg(colors.muted()))
+            } else {
+                ListItem::new(content).style(style)
+            }
+        })
+        .collect();
+
+    let border_color = if app.tag_focus == PaneFocus::List {
+        colors.accent()
+    } else {
+        colors.muted()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(borders)
+                .border_style(Style::default().fg(border_color))
+                .title(format!("Tags ({})", tags.len()))
+        )
+        .highlight_style(Style::default().bg(colors.muted()));
+
+    f.render_widget(list, area);
+}
+
+fn render_tagged_entries(f: &mut Frame, app: &App, area: Rect) {
+    let config = app.config.get_config();
+    let colors = &config.theme.colors;
+    let borders = config.layout.border_style.to_ratatui_border();
+
+    let Some(tag) = app.selected_tag() else {
+        let help_msg = Paragraph::new("Select a tag with j/k and press Enter to see its entries")
+            .style(Style::default().fg(colors.muted()))
+            .block(Block::default().borders(borders).title("Entries"))
+            .alignment(Alignment::Center);
+        f.render_widget(help_msg, area);
+        return;
+    };
+
+    let focused = app.tag_focus == PaneFocus::Entries;
+    let border_color = if focused { colors.accent() } else { colors.muted() };
+
+    let entries = app.journal.entries_with_tag(tag);
+    let selected = if focused { app.selected_entry } else { None };
+    let list = create_entry_list(&entries, selected, app)
+        .block(
+            Block::default()
+                .borders(borders)
+                .border_style(Style::default().fg(border_color))
+                .title(format!("{} ({})", tag_label(tag), entries.len()))
+        );
+
+    f.render_widget(list, area);
+}
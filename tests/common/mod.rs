@@ -0,0 +1,37 @@
+//! Runs the `bujo` binary against a throwaway home, away from the user's
+//! journal, config and environment.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// A fresh home directory under the system temp dir, unique to the test.
+pub fn empty_home(test: &str) -> PathBuf {
+    let home = std::env::temp_dir().join(format!("bujo-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&home);
+    fs::create_dir_all(home.join("config")).unwrap();
+    home
+}
+
+pub fn bujo(home: &Path, args: &[&str]) -> Output {
+    bujo_with_env(home, args, &[])
+}
+
+/// Runs bujo with data and config in `home` and only the given `BUJO_*` variables set.
+pub fn bujo_with_env(home: &Path, args: &[&str], vars: &[(&str, &str)]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_bujo"))
+        .args(args)
+        .env("XDG_DATA_HOME", home.join("data"))
+        .env("XDG_CONFIG_HOME", home.join("config"))
+        .env_remove("BUJO_PASSPHRASE")
+        .env_remove("BUJO_JOURNAL")
+        .envs(vars.iter().copied())
+        .output()
+        .unwrap()
+}
+
+/// The standard output of a run that must have succeeded.
+pub fn succeeds(output: Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
@@ -0,0 +1,58 @@
+//! Round-trips a journal through `bujo encrypt` and `bujo decrypt`, with the
+//! passphrase supplied through `BUJO_PASSPHRASE`.
+
+// The data directory is redirected through XDG_DATA_HOME, which only Linux honours.
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::{bujo_with_env, empty_home, succeeds};
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+fn bujo(home: &Path, passphrase: Option<&str>, args: &[&str]) -> Output {
+    match passphrase {
+        Some(passphrase) => bujo_with_env(home, args, &[("BUJO_PASSPHRASE", passphrase)]),
+        None => common::bujo(home, args),
+    }
+}
+
+
+#[test]
+fn encrypted_journal_round_trips() {
+    let home = empty_home("encrypt");
+    let data_dir = home.join("data/bujo");
+    succeeds(bujo(&home, None, &["add", "note", "meet the informant", "--date", "2026-01-05"]));
+
+    succeeds(bujo(&home, Some("correct horse"), &["encrypt"]));
+    assert!(!data_dir.join("journal.json").exists());
+    let sealed = fs::read(data_dir.join("journal.enc")).unwrap();
+    assert!(!String::from_utf8_lossy(&sealed).contains("informant"));
+
+    succeeds(bujo(&home, Some("correct horse"), &["add", "task", "burn the notes", "--date", "2026-01-05"]));
+    let listed = succeeds(bujo(&home, Some("correct horse"), &["list", "--date", "2026-01-05"]));
+    assert!(listed.contains("meet the informant") && listed.contains("burn the notes"));
+
+    succeeds(bujo(&home, Some("correct horse"), &["decrypt"]));
+    assert!(!data_dir.join("journal.enc").exists());
+    let listed = succeeds(bujo(&home, None, &["list", "--date", "2026-01-05"]));
+    assert!(listed.contains("burn the notes"));
+
+    fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn wrong_passphrase_is_reported() {
+    let home = empty_home("wrong-passphrase");
+    succeeds(bujo(&home, None, &["add", "note", "private"]));
+    succeeds(bujo(&home, Some("right"), &["encrypt"]));
+    let before = fs::read(home.join("data/bujo/journal.enc")).unwrap();
+
+    let output = bujo(&home, Some("wrong"), &["add", "note", "should not be written"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Wrong passphrase"));
+    assert_eq!(fs::read(home.join("data/bujo/journal.enc")).unwrap(), before);
+
+    fs::remove_dir_all(home).unwrap();
+}
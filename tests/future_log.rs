@@ -0,0 +1,33 @@
+//! Plans entries for whole months with `bujo add --month`.
+
+// The data directory is redirected through XDG_DATA_HOME, which only Linux honours.
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::{bujo, empty_home, succeeds};
+use std::fs;
+
+#[test]
+fn whole_month_entries_head_their_month_but_not_its_first_day() {
+    let home = empty_home("future-month");
+    succeeds(bujo(&home, &["add", "event", "Dentist", "--date", "2031-12-01"]));
+    let added = succeeds(bujo(&home, &["add", "task", "Renew passport", "--month", "2031-12"]));
+    assert!(added.starts_with("2031-12 "), "{}", added);
+
+    let month = succeeds(bujo(&home, &["list", "--month", "2031-12"]));
+    let position = |text: &str| month.find(text).unwrap_or_else(|| panic!("{} not in {}", text, month));
+    assert!(position("December 2031") < position("Renew passport"));
+    assert!(position("Renew passport") < position("Dentist"));
+
+    let day = succeeds(bujo(&home, &["list", "--date", "2031-12-01"]));
+    assert!(day.contains("Dentist") && !day.contains("Renew passport"), "{}", day);
+
+    let json = succeeds(bujo(&home, &["future", "--format", "json"]));
+    assert!(json.contains("\"whole_month\": true"), "{}", json);
+
+    let output = bujo(&home, &["add", "task", "Both", "--month", "2031-12", "--date", "2031-12-02"]);
+    assert!(!output.status.success());
+
+    fs::remove_dir_all(home).unwrap();
+}
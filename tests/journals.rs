@@ -0,0 +1,34 @@
+//! Picks the journal to open with `--journal` and `BUJO_JOURNAL`.
+
+// The data directory is redirected through XDG_DATA_HOME, which only Linux honours.
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::{bujo, bujo_with_env, empty_home, succeeds};
+use std::fs;
+
+#[test]
+fn the_journal_flag_wins_over_the_environment() {
+    let home = empty_home("journal-precedence");
+    let from_env = home.join("from-env");
+    let from_flag = home.join("from-flag");
+    let env = [("BUJO_JOURNAL", from_env.to_str().unwrap())];
+
+    succeeds(bujo_with_env(&home, &["add", "note", "Picked by env"], &env));
+    let flag = from_flag.to_str().unwrap();
+    succeeds(bujo_with_env(&home, &["--journal", flag, "add", "note", "Picked by flag"], &env));
+
+    let listed = succeeds(bujo_with_env(&home, &["list"], &env));
+    assert!(listed.contains("Picked by env") && !listed.contains("Picked by flag"), "{}", listed);
+    let listed = succeeds(bujo(&home, &["--journal", flag, "list"]));
+    assert!(listed.contains("Picked by flag") && !listed.contains("Picked by env"), "{}", listed);
+    let listed = succeeds(bujo(&home, &["list"]));
+    assert!(!listed.contains("Picked by"), "{}", listed);
+
+    let output = bujo_with_env(&home, &["list"], &[("BUJO_JOURNAL", "hobby")]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No journal named 'hobby'"));
+
+    fs::remove_dir_all(home).unwrap();
+}
@@ -0,0 +1,86 @@
+//! Loads the journal fixtures in `tests/fixtures`, one per schema version ever
+//! written, through the `bujo` binary. When the schema version is bumped, add a
+//! `journal-vN.json` fixture for it and list it in `FIXTURES`.
+
+// The data directory is redirected through XDG_DATA_HOME, which only Linux honours.
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::{bujo, empty_home, succeeds};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+const FIXTURES: &[&str] = &[
+    "journal-v0.json",
+    "journal-v1.json",
+    "journal-v2.json",
+    "journal-v3.json",
+    "journal-v4.json",
+    "journal-v5.json",
+];
+
+/// Sets up a throwaway home whose journal is a copy of the fixture.
+fn home_with_fixture(fixture: &str, test: &str) -> PathBuf {
+    let home = empty_home(&format!("{}-{}", test, fixture));
+    let data_dir = home.join("data").join("bujo");
+    fs::create_dir_all(&data_dir).unwrap();
+    let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(fixture);
+    fs::copy(source, data_dir.join("journal.json")).unwrap();
+    home
+}
+
+fn json_output(output: Output) -> Value {
+    serde_json::from_str(&succeeds(output)).unwrap()
+}
+
+#[test]
+fn every_fixture_loads() {
+    for fixture in FIXTURES {
+        let home = home_with_fixture(fixture, "load");
+
+        let entries = json_output(bujo(&home, &["list", "--from", "2000-01-01", "--format", "json"]));
+        assert_eq!(entries.as_array().unwrap().len(), 3, "{}", fixture);
+        assert_eq!(entries[0]["content"], "Renew passport", "{}", fixture);
+        assert_eq!(entries[0]["tags"][0], "errands", "{}", fixture);
+
+        let collections = json_output(bujo(&home, &["collections", "--format", "json"]));
+        assert_eq!(collections[0]["entries"].as_array().unwrap().len(), 1, "{}", fixture);
+
+        fs::remove_dir_all(home).unwrap();
+    }
+}
+
+#[test]
+fn saving_upgrades_to_the_current_version() {
+    for fixture in FIXTURES {
+        let home = home_with_fixture(fixture, "upgrade");
+
+        succeeds(bujo(&home, &["add", "note", "after upgrade", "--date", "2025-03-03"]));
+
+        let path = home.join("data/bujo/journal.json");
+        let journal: Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        let latest = FIXTURES.len() as u64 - 1;
+        assert_eq!(journal["schema_version"], latest, "{}", fixture);
+        let entries = journal["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 4, "{}", fixture);
+        assert!(entries.iter().all(|entry| entry["status_history"].is_array()), "{}", fixture);
+
+        fs::remove_dir_all(home).unwrap();
+    }
+}
+
+#[test]
+fn newer_schema_is_refused() {
+    let home = home_with_fixture("journal-future.json", "future");
+    let before = fs::read(home.join("data/bujo/journal.json")).unwrap();
+
+    let output = bujo(&home, &["add", "note", "would drop the reminder field"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("schema version 99"));
+    assert_eq!(fs::read(home.join("data/bujo/journal.json")).unwrap(), before);
+
+    fs::remove_dir_all(home).unwrap();
+}
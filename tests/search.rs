@@ -0,0 +1,84 @@
+//! Runs `bujo search` queries against a small journal.
+
+// The data directory is redirected through XDG_DATA_HOME, which only Linux honours.
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::{bujo, empty_home, succeeds};
+use std::fs;
+
+#[test]
+fn queries_filter_entries() {
+    let home = empty_home("search");
+    succeeds(bujo(&home, &["add", "task", "** Review the release notes #work", "--date", "2026-09-14"]));
+    succeeds(bujo(&home, &["add", "task", "Book flights #travel", "--date", "2026-10-02"]));
+    succeeds(bujo(&home, &["add", "note", "release party was fun #work", "--date", "2026-11-20"]));
+
+    let search = |query: &str| succeeds(bujo(&home, &["search", query]));
+
+    let found = search("type:task tag:work date:2026-09..2026-10 priority:>1");
+    assert!(found.contains("Review the release notes") && !found.contains("Book flights"));
+
+    let found = search("release -type:note");
+    assert!(found.contains("Review the release notes") && !found.contains("release party"));
+
+    let found = search("flihgts");
+    assert!(found.contains("Book flights"), "{}", found);
+
+    let found = search("\"party was\" status:incomplete");
+    assert!(found.trim().is_empty(), "{}", found);
+
+    let output = bujo(&home, &["search", "kind:task"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown filter 'kind:'"));
+
+    fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn closer_matches_rank_first() {
+    let home = empty_home("search-rank");
+    succeeds(bujo(&home, &["add", "task", "Ask about the renewal fee", "--date", "2026-03-02"]));
+    succeeds(bujo(&home, &["add", "task", "Renew passport", "--date", "2026-03-02"]));
+    succeeds(bujo(&home, &["add", "task", "Find the rnw form", "--date", "2026-03-02"]));
+
+    let found = succeeds(bujo(&home, &["search", "renew"]));
+    let position = |text: &str| found.find(text).unwrap_or_else(|| panic!("{} not in {}", text, found));
+    assert!(position("Renew passport") < position("renewal fee"));
+    assert!(!found.contains("rnw form"));
+
+    fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn saved_searches_rerun_against_the_current_journal() {
+    let home = empty_home("search-saved");
+    let today = chrono::Local::now().date_naive().to_string();
+    succeeds(bujo(&home, &["add", "task", "File the expense report #work", "--date", "2020-01-05"]));
+    succeeds(bujo(&home, &["add", "task", "Reply to the auditor #work", "--date", &today]));
+
+    let found = succeeds(bujo(&home, &["search", "type:task tag:work date:..today-3", "--save", "stale work"]));
+    assert!(found.contains("expense report") && !found.contains("auditor"), "{}", found);
+
+    succeeds(bujo(&home, &["add", "task", "Chase the invoice #work", "--date", "2020-02-11"]));
+    let found = succeeds(bujo(&home, &["search", "--saved", "stale work"]));
+    assert!(found.contains("expense report") && found.contains("Chase the invoice"), "{}", found);
+
+    let output = bujo(&home, &["search", "--saved", "stale home"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("try stale work"));
+
+    fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn tags_given_to_add_are_stored_like_inline_tags() {
+    let home = empty_home("search-tags");
+    succeeds(bujo(&home, &["add", "task", "Buy stamps", "--tag", "#Errands", "--tag", "@Town"]));
+
+    let json = succeeds(bujo(&home, &["search", "tag:errands tag:@town", "--format", "json"]));
+    assert!(json.contains("\"errands\"") && json.contains("\"@town\""), "{}", json);
+
+    fs::remove_dir_all(home).unwrap();
+}